@@ -13,7 +13,7 @@ fn evaluate() {
     ];
     let players = vec!["TT+".parse().unwrap(), "A8s+".parse().unwrap()];
 
-    let evaluator = FlopExhaustiveEvaluator::new(&board, &players);
+    let evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
     let mut player_results = vec![HashMap::new(); players.len()];
 
     for (player_index, player) in players.iter().enumerate() {
@@ -23,7 +23,7 @@ fn evaluate() {
     }
 
     for showdown in evaluator {
-        for (player_index, player) in showdown.players().into_iter().enumerate() {
+        for (player_index, player) in showdown.players().iter().enumerate() {
             player_results[player_index]
                 .get_mut(&player.hole_cards())
                 .unwrap()
@@ -40,7 +40,7 @@ fn evaluate() {
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("base", |b| b.iter(|| evaluate()));
+    c.bench_function("base", |b| b.iter(evaluate));
 }
 
 criterion_group! {
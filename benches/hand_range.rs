@@ -0,0 +1,39 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use espada::hand_range::{HandRange, HandRangeToken};
+use std::str::FromStr;
+
+const TOKENS: [&str; 12] = [
+    "QQ+", "AKs", "AQo:0.5", "88-66", "ATs-A8s", "T9s-54s", "65s+", "AsKd", "KhXx", "AsKx",
+    "Axs:0.25", "QJo+",
+];
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hand range");
+    let wide_range = TOKENS.join(",");
+
+    group.bench_function("parse tokens", |b| {
+        b.iter(|| {
+            TOKENS
+                .iter()
+                .map(|token| HandRangeToken::from_str(black_box(token)).unwrap())
+                .collect::<Vec<HandRangeToken>>()
+        })
+    });
+
+    group.bench_function("parse narrow range", |b| {
+        b.iter(|| HandRange::from_str(black_box("QQ+,AKs,AKo:0.5")).unwrap())
+    });
+
+    group.bench_function("parse wide range", |b| {
+        b.iter(|| HandRange::from_str(black_box(&wide_range)).unwrap())
+    });
+
+    group.bench_function("parse invalid range", |b| {
+        b.iter(|| HandRange::from_str(black_box("QQ+,AKs,AKo:0.5,T9s-53s")).unwrap_err())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
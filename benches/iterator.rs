@@ -0,0 +1,96 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use espada::card::parse_board;
+use espada::evaluator::{FlopExhaustiveEvaluator, HeadsUpEvaluator};
+use espada::hand_range::HandRange;
+
+fn iterate(board: &str, players: &[&str], scope: (u8, u8, u8, u8)) -> usize {
+    let players = players
+        .iter()
+        .map(|player| player.parse().unwrap())
+        .collect::<Vec<HandRange>>();
+    let mut evaluator =
+        FlopExhaustiveEvaluator::new(&parse_board(board).unwrap(), &players).unwrap();
+
+    evaluator.scope(scope.0, scope.1, scope.2, scope.3);
+
+    evaluator.into_iter().map(black_box).count()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iterator");
+
+    group.bench_function("two players", |b| {
+        b.iter(|| iterate("Ks8d2h", &["TT+", "A8s+"], (0, 1, 48, 49)))
+    });
+
+    // most of the combinations are blocked by the other players
+    group.bench_function("overlapping ranges", |b| {
+        b.iter(|| {
+            iterate(
+                "Ks8d2h",
+                &["AA,AKs,AKo", "AA,AKs,AKo", "AA,AKs,AKo"],
+                (0, 1, 4, 5),
+            )
+        })
+    });
+
+    group.bench_function("wide ranges", |b| {
+        b.iter(|| {
+            iterate(
+                "Ks8d2h",
+                &["22+,A2s+,K9s+,ATo+", "22+,A2s+,K9s+,ATo+"],
+                (0, 1, 1, 2),
+            )
+        })
+    });
+
+    group.bench_function("wide ranges equities", |b| {
+        b.iter(|| {
+            let players = ["22+,A2s+,K9s+,ATo+", "22+,A2s+,K9s+,ATo+"]
+                .iter()
+                .map(|player| player.parse().unwrap())
+                .collect::<Vec<HandRange>>();
+            let mut evaluator =
+                FlopExhaustiveEvaluator::new(&parse_board("Ks8d2h").unwrap(), &players).unwrap();
+
+            evaluator.scope(0, 1, 1, 2);
+
+            black_box(evaluator.equities())
+        })
+    });
+
+    group.bench_function("wide ranges heads-up sweep", |b| {
+        b.iter(|| {
+            let players = [
+                "22+,A2s+,K9s+,ATo+".parse().unwrap(),
+                "22+,A2s+,K9s+,ATo+".parse().unwrap(),
+            ];
+            let mut evaluator =
+                HeadsUpEvaluator::new(&parse_board("Ks8d2h").unwrap(), &players).unwrap();
+
+            evaluator.scope(0, 1, 1, 2);
+
+            black_box(evaluator.equities())
+        })
+    });
+
+    group.bench_function("narrow ranges heads-up sweep", |b| {
+        b.iter(|| {
+            let players = ["AsAh".parse().unwrap(), "QsQh".parse().unwrap()];
+            let evaluator =
+                HeadsUpEvaluator::new(&parse_board("Ks8d2h").unwrap(), &players).unwrap();
+
+            black_box(evaluator.equities())
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group! {
+    name = iterator;
+    config = Criterion::default();
+    targets = criterion_benchmark
+}
+
+criterion_main!(iterator);
@@ -0,0 +1,88 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use espada::card::{Card, Deck};
+use espada::evaluator::{
+    Backend, CompactBackend, MadeHand, RankKeyBackend, ScalarBackend, TableBackend,
+};
+use espada::hand_range::CardPair;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fn random_hands(len: usize) -> Vec<[Card; 7]> {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    (0..len)
+        .map(|_| {
+            let mut deck = Deck::new();
+
+            deck.shuffle(&mut rng);
+            deck.deal_n(7).unwrap().try_into().unwrap()
+        })
+        .collect()
+}
+
+fn evaluate<B: Backend>(hands: &[[Card; 7]]) -> Vec<MadeHand> {
+    hands.iter().map(B::evaluate).collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("made hand");
+    let hands = random_hands(10000);
+    let board: [Card; 5] = hands[0][2..].try_into().unwrap();
+    let hole_cards = hands
+        .iter()
+        .map(|cards| CardPair::new(cards[0], cards[1]))
+        .filter(|card_pair| !board.contains(&card_pair[0]) && !board.contains(&card_pair[1]))
+        .collect::<Vec<CardPair>>();
+
+    // the card by card evaluation that the batch ones are compared to
+    group.bench_function("scalar", |b| {
+        b.iter(|| evaluate::<ScalarBackend>(black_box(&hands)))
+    });
+
+    group.bench_function("batch", |b| {
+        b.iter(|| MadeHand::from_batch(black_box(&hands)))
+    });
+
+    group.bench_function("scalar with board", |b| {
+        b.iter(|| {
+            hole_cards
+                .iter()
+                .map(|card_pair| {
+                    ScalarBackend::evaluate(&[
+                        card_pair[0],
+                        card_pair[1],
+                        board[0],
+                        board[1],
+                        board[2],
+                        board[3],
+                        board[4],
+                    ])
+                })
+                .collect::<Vec<MadeHand>>()
+        })
+    });
+
+    group.bench_function("batch with board", |b| {
+        b.iter(|| MadeHand::from_batch_with_board(&board, black_box(&hole_cards)))
+    });
+
+    group.bench_function("table backend", |b| {
+        b.iter(|| evaluate::<TableBackend>(black_box(&hands)))
+    });
+
+    group.bench_function("compact backend", |b| {
+        b.iter(|| evaluate::<CompactBackend>(black_box(&hands)))
+    });
+
+    // the table is built before measuring
+    evaluate::<RankKeyBackend>(&hands);
+
+    group.bench_function("rank key backend", |b| {
+        b.iter(|| evaluate::<RankKeyBackend>(black_box(&hands)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
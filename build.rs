@@ -0,0 +1,17 @@
+use std::env;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+#[path = "src/evaluator/dp_table/generator.rs"]
+mod generator;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/evaluator/dp_table/generator.rs");
+
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("dp_table.rs");
+    let mut writer = BufWriter::new(File::create(path).unwrap());
+
+    generator::HOLDEM.write_tables(&mut writer).unwrap();
+}
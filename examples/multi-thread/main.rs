@@ -59,7 +59,7 @@ fn main() {
                 }
             }
 
-            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players);
+            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
             evaluator.scope(
                 scope.turn_from,
                 scope.river_from,
@@ -68,7 +68,7 @@ fn main() {
             );
 
             for showdown in evaluator {
-                for (player_index, player) in showdown.players().into_iter().enumerate() {
+                for (player_index, player) in showdown.players().iter().enumerate() {
                     player_results[player_index]
                         .get_mut(&player.hole_cards())
                         .unwrap()
@@ -78,8 +78,7 @@ fn main() {
                         player_results[player_index]
                             .get_mut(&player.hole_cards())
                             .unwrap()
-                            .0 +=
-                            1.0 / showdown.winner_len() as f64 * showdown.probability() as f64;
+                            .0 += 1.0 / showdown.winner_len() as f64 * showdown.probability();
                     }
                 }
 
@@ -39,12 +39,12 @@ fn main() {
 
     println!("space: {} patterns", space);
 
-    let evaluator = FlopExhaustiveEvaluator::new(&board, &players);
+    let evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
 
     let instant = std::time::Instant::now();
 
     for showdown in evaluator {
-        for (player_index, player) in showdown.players().into_iter().enumerate() {
+        for (player_index, player) in showdown.players().iter().enumerate() {
             player_results[player_index]
                 .get_mut(&player.hole_cards())
                 .unwrap()
@@ -54,7 +54,7 @@ fn main() {
                 player_results[player_index]
                     .get_mut(&player.hole_cards())
                     .unwrap()
-                    .0 += 1.0 / showdown.winner_len() as f64 * showdown.probability() as f64;
+                    .0 += 1.0 / showdown.winner_len() as f64 * showdown.probability();
             }
         }
 
@@ -0,0 +1,358 @@
+use crate::output::OutputFormat;
+use espada::card::{parse_cards, Card, CardSet};
+use espada::hand_range::RangeFormat;
+use std::fmt::{Display, Formatter};
+
+pub const USAGE: &str = "\
+usage: espada <command> [options] [arguments]
+
+commands:
+  equity <range> <range>...   equity of each range against the others
+  stats <range>...            number of combos of each range
+  evaluate <cards>...         hand made by each set of hole cards on the board
+  convert <range>             rewrites a range in another format. \"-\" reads it from stdin
+  help                        prints this message
+
+options:
+  -b, --board <cards>         board cards, e.g. Ks8d2h
+  -d, --dead <cards>          cards that can't be dealt to anyone
+  -t, --threads <n>           number of threads (default: available parallelism)
+      --exhaustive            evaluates every runout. needs at least a flop
+      --monte-carlo           evaluates random runouts. the default without a flop
+  -n, --iterations <n>        number of monte carlo samples (default: 100000)
+      --seed <n>              seed of monte carlo samples (default: random)
+  -f, --format <format>       table, json or csv (default: table)
+      --from <format>         range format of convert input (default: espada)
+      --to <format>           range format of convert output (default: espada)
+  -h, --help                  prints this message
+
+range formats: espada, pio, pio-weights, gtoplus, flopzilla
+";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Command {
+    Equity,
+    Stats,
+    Evaluate,
+    Convert,
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Method {
+    Exhaustive,
+    MonteCarlo,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Args {
+    pub command: Command,
+    pub arguments: Vec<String>,
+    pub board: Vec<Card>,
+    pub dead_cards: CardSet,
+    pub threads: usize,
+    // `None` leaves the choice to the command
+    pub method: Option<Method>,
+    pub iterations: u64,
+    pub seed: Option<u64>,
+    pub format: OutputFormat,
+    // `None` is the notation of espada itself, e.g. "QQ+,AKs:0.5"
+    pub from: Option<RangeFormat>,
+    pub to: Option<RangeFormat>,
+}
+
+impl Args {
+    // `args` doesn't include the program name
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Args, ArgsError> {
+        let mut args = args.into_iter();
+        let command = match args.next().as_deref() {
+            Some("equity") => Command::Equity,
+            Some("stats") => Command::Stats,
+            Some("evaluate") => Command::Evaluate,
+            Some("convert") => Command::Convert,
+            Some("help" | "-h" | "--help") => Command::Help,
+            Some(command) => return Err(ArgsError::UnknownCommand(command.to_string())),
+            None => return Err(ArgsError::MissingCommand),
+        };
+        let mut parsed = Args {
+            command,
+            arguments: vec![],
+            board: vec![],
+            dead_cards: CardSet::empty(),
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            method: None,
+            iterations: 100_000,
+            seed: None,
+            format: OutputFormat::Table,
+            from: None,
+            to: None,
+        };
+
+        while let Some(arg) = args.next() {
+            // "-" stands for stdin and is an argument
+            if !arg.starts_with('-') || arg == "-" {
+                parsed.arguments.push(arg);
+
+                continue;
+            }
+
+            // both "--board Ks8d2h" and "--board=Ks8d2h" are accepted
+            let (option, inline_value) = match arg.split_once('=') {
+                Some((option, value)) => (option.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            match option.as_str() {
+                "-h" | "--help" => parsed.command = Command::Help,
+                "--exhaustive" => parsed.method = Some(Method::Exhaustive),
+                "--monte-carlo" => parsed.method = Some(Method::MonteCarlo),
+                "-b" | "--board" | "-d" | "--dead" | "-t" | "--threads" | "-n" | "--iterations"
+                | "--seed" | "-f" | "--format" | "--from" | "--to" => {
+                    let value = inline_value
+                        .or_else(|| args.next())
+                        .ok_or_else(|| ArgsError::MissingValue(option.clone()))?;
+
+                    parsed.set(&option, &value)?;
+                }
+                _ => return Err(ArgsError::UnknownOption(option)),
+            }
+        }
+
+        if !parsed
+            .dead_cards
+            .is_disjoint(&parsed.board.iter().collect())
+        {
+            return Err(ArgsError::OverlappingCards);
+        }
+
+        Ok(parsed)
+    }
+
+    fn set(&mut self, option: &str, value: &str) -> Result<(), ArgsError> {
+        let invalid = || ArgsError::InvalidValue(option.to_string(), value.to_string());
+
+        match option {
+            "-b" | "--board" => {
+                self.board = parse_distinct_cards(value).ok_or_else(invalid)?;
+
+                if self.board.len() > 5 {
+                    return Err(invalid());
+                }
+            }
+            "-d" | "--dead" => {
+                self.dead_cards = parse_distinct_cards(value)
+                    .ok_or_else(invalid)?
+                    .into_iter()
+                    .collect();
+            }
+            "-t" | "--threads" => {
+                self.threads = value
+                    .parse()
+                    .ok()
+                    .filter(|threads| *threads > 0)
+                    .ok_or_else(invalid)?;
+            }
+            "-n" | "--iterations" => {
+                self.iterations = value
+                    .parse()
+                    .ok()
+                    .filter(|iterations| *iterations > 0)
+                    .ok_or_else(invalid)?;
+            }
+            "--seed" => self.seed = Some(value.parse().map_err(|_| invalid())?),
+            "-f" | "--format" => {
+                self.format = match value {
+                    "table" => OutputFormat::Table,
+                    "json" => OutputFormat::Json,
+                    "csv" => OutputFormat::Csv,
+                    _ => return Err(invalid()),
+                };
+            }
+            "--from" => self.from = parse_range_format(value).ok_or_else(invalid)?,
+            "--to" => self.to = parse_range_format(value).ok_or_else(invalid)?,
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+}
+
+// cards without wildcards or duplicates, e.g. "Ks8d2h" or "Ks 8d 2h"
+pub fn parse_distinct_cards(value: &str) -> Option<Vec<Card>> {
+    let cards = parse_cards(value)
+        .ok()?
+        .into_iter()
+        .collect::<Option<Vec<Card>>>()?;
+
+    (cards.iter().collect::<CardSet>().len() == cards.len()).then_some(cards)
+}
+
+fn parse_range_format(value: &str) -> Option<Option<RangeFormat>> {
+    match value {
+        "espada" => Some(None),
+        "pio" => Some(Some(RangeFormat::PioSolver)),
+        "pio-weights" => Some(Some(RangeFormat::PioSolverWeights)),
+        "gtoplus" => Some(Some(RangeFormat::GtoPlus)),
+        "flopzilla" => Some(Some(RangeFormat::Flopzilla)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ArgsError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownOption(String),
+    MissingValue(String),
+    InvalidValue(String, String),
+    OverlappingCards,
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ArgsError::MissingCommand => write!(f, "no command is given."),
+            ArgsError::UnknownCommand(command) => write!(f, "{} is not a command.", command),
+            ArgsError::UnknownOption(option) => write!(f, "{} is not an option.", option),
+            ArgsError::MissingValue(option) => write!(f, "{} needs a value.", option),
+            ArgsError::InvalidValue(option, value) => {
+                write!(f, "{} is not a valid value for {}.", value, option)
+            }
+            ArgsError::OverlappingCards => {
+                write!(f, "the dead cards and the board share a card.")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn parse(args: &[&str]) -> Result<Args, ArgsError> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn it_parses_command_and_arguments() {
+            let args = parse(&["equity", "QQ+", "AKs,AKo"]).unwrap();
+
+            assert_eq!(args.command, Command::Equity);
+            assert_eq!(args.arguments, vec!["QQ+", "AKs,AKo"]);
+            assert_eq!(args.board, vec![]);
+            assert_eq!(args.method, None);
+            assert_eq!(args.format, OutputFormat::Table);
+        }
+
+        #[test]
+        fn it_parses_options() {
+            let args = parse(&[
+                "equity",
+                "QQ+",
+                "--board",
+                "Ks8d2h",
+                "-d=7c",
+                "-t",
+                "4",
+                "--monte-carlo",
+                "-n",
+                "1000",
+                "--seed=42",
+                "-f",
+                "json",
+                "AKs",
+            ])
+            .unwrap();
+
+            assert_eq!(args.arguments, vec!["QQ+", "AKs"]);
+            assert_eq!(
+                args.board,
+                vec![
+                    Card::from_str("Ks").unwrap(),
+                    Card::from_str("8d").unwrap(),
+                    Card::from_str("2h").unwrap()
+                ]
+            );
+            assert_eq!(args.dead_cards, CardSet::from_str("7c").unwrap());
+            assert_eq!(args.threads, 4);
+            assert_eq!(args.method, Some(Method::MonteCarlo));
+            assert_eq!(args.iterations, 1000);
+            assert_eq!(args.seed, Some(42));
+            assert_eq!(args.format, OutputFormat::Json);
+        }
+
+        #[test]
+        fn it_parses_range_formats() {
+            let args = parse(&["convert", "-", "--from", "gtoplus", "--to", "espada"]).unwrap();
+
+            assert_eq!(args.arguments, vec!["-"]);
+            assert_eq!(args.from, Some(RangeFormat::GtoPlus));
+            assert_eq!(args.to, None);
+        }
+
+        #[test]
+        fn it_parses_help() {
+            assert_eq!(parse(&["help"]).unwrap().command, Command::Help);
+            assert_eq!(parse(&["stats", "-h"]).unwrap().command, Command::Help);
+        }
+
+        #[test]
+        fn it_returns_error_when_command_is_unknown() {
+            assert_eq!(parse(&[]), Err(ArgsError::MissingCommand));
+            assert_eq!(
+                parse(&["odds"]),
+                Err(ArgsError::UnknownCommand("odds".to_string()))
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_option_is_invalid() {
+            assert_eq!(
+                parse(&["stats", "--board"]),
+                Err(ArgsError::MissingValue("--board".to_string()))
+            );
+            assert_eq!(
+                parse(&["stats", "--turn", "Ks"]),
+                Err(ArgsError::UnknownOption("--turn".to_string()))
+            );
+            assert_eq!(
+                parse(&["stats", "-t", "0"]),
+                Err(ArgsError::InvalidValue("-t".to_string(), "0".to_string()))
+            );
+            assert_eq!(
+                parse(&["equity", "-n", "0"]),
+                Err(ArgsError::InvalidValue("-n".to_string(), "0".to_string()))
+            );
+            assert_eq!(
+                parse(&["stats", "-f", "xml"]),
+                Err(ArgsError::InvalidValue("-f".to_string(), "xml".to_string()))
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_cards_are_invalid() {
+            assert_eq!(
+                parse(&["stats", "-b", "KsKs2h"]),
+                Err(ArgsError::InvalidValue(
+                    "-b".to_string(),
+                    "KsKs2h".to_string()
+                ))
+            );
+            assert_eq!(
+                parse(&["stats", "-b", "AsKsQsJsTs9s"]),
+                Err(ArgsError::InvalidValue(
+                    "-b".to_string(),
+                    "AsKsQsJsTs9s".to_string()
+                ))
+            );
+            assert_eq!(
+                parse(&["stats", "-b", "Ks8d2h", "-d", "2h"]),
+                Err(ArgsError::OverlappingCards)
+            );
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use crate::args::{parse_distinct_cards, Args, Method};
+use crate::equity::{exhaustive, monte_carlo};
+use crate::output::{Table, Value};
+use espada::card::{Card, CardSet};
+use espada::evaluator::{MadeHand, MadeHandType};
+use espada::hand_range::{
+    HandRange, InvalidHandRangeError, ParseHandRangeError, ParseRangeFormatError,
+    WriteRangeFormatError,
+};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// from the strongest to the weakest
+const MADE_HAND_TYPES: [MadeHandType; 9] = [
+    MadeHandType::StraightFlush,
+    MadeHandType::Quads,
+    MadeHandType::FullHouse,
+    MadeHandType::Flush,
+    MadeHandType::Straight,
+    MadeHandType::Trips,
+    MadeHandType::TwoPair,
+    MadeHandType::Pair,
+    MadeHandType::HighCard,
+];
+
+pub fn equity(args: &Args) -> Result<Table, CommandError> {
+    if args.arguments.len() < 2 {
+        return Err(CommandError::MissingArgument("two or more ranges"));
+    }
+
+    let players = parse_ranges(&args.arguments)?;
+    let method = args.method.unwrap_or(if args.board.len() >= 3 {
+        Method::Exhaustive
+    } else {
+        Method::MonteCarlo
+    });
+    let accumulator = match method {
+        Method::Exhaustive => exhaustive(&players, &args.board, &args.dead_cards, args.threads)?,
+        Method::MonteCarlo => monte_carlo(
+            &players,
+            &args.board,
+            &args.dead_cards,
+            args.iterations,
+            args.threads,
+            args.seed.unwrap_or_else(random_seed),
+        )?,
+    };
+
+    if accumulator.showdown_len() == 0 {
+        return Err(CommandError::NoDeal);
+    }
+
+    let mut table = Table::new(&["player", "range", "equity"]);
+
+    for (i, player) in players.iter().enumerate() {
+        table.push(vec![
+            Value::Integer(i as u64 + 1),
+            Value::Text(player.to_string()),
+            Value::Float(accumulator.equity(i), 4),
+        ]);
+    }
+
+    Ok(table)
+}
+
+// combos of each range. the combos of each made hand type are added on a full board.
+pub fn stats(args: &Args) -> Result<Table, CommandError> {
+    if args.arguments.is_empty() {
+        return Err(CommandError::MissingArgument("a range"));
+    }
+
+    let hand_ranges = parse_ranges(&args.arguments)?;
+    let board: Option<[Card; 5]> = args.board.clone().try_into().ok();
+    let known_cards = args.board.iter().collect::<CardSet>() | args.dead_cards;
+    let mut headers = vec!["range", "combos", "percent", "available combos"];
+
+    if board.is_some() {
+        headers.extend(MADE_HAND_TYPES.map(made_hand_type_name));
+    }
+
+    let mut table = Table::new(&headers);
+
+    for hand_range in &hand_ranges {
+        let mut row = vec![
+            Value::Text(hand_range.to_string()),
+            Value::Float(hand_range.combos() as f64, 2),
+            Value::Float(hand_range.percent_of_all_hands() as f64, 2),
+            Value::Float(hand_range.combos_excluding(&known_cards) as f64, 2),
+        ];
+
+        if let Some(board) = &board {
+            let combos = hand_range
+                .excluding(&args.dead_cards)
+                .made_hand_type_combos(board);
+
+            row.extend(MADE_HAND_TYPES.map(|hand_type| {
+                Value::Float(combos.get(&hand_type).copied().unwrap_or(0.0) as f64, 2)
+            }));
+        }
+
+        table.push(row);
+    }
+
+    Ok(table)
+}
+
+// each argument is either hole cards completed by the board or seven cards
+pub fn evaluate(args: &Args) -> Result<Table, CommandError> {
+    if args.arguments.is_empty() {
+        return Err(CommandError::MissingArgument("cards"));
+    }
+
+    let mut table = Table::new(&["cards", "hand", "power index"]);
+
+    for argument in &args.arguments {
+        let invalid = || CommandError::InvalidCards(argument.clone());
+        let cards = parse_distinct_cards(argument).ok_or_else(invalid)?;
+        let all_cards: [Card; 7] = [cards.as_slice(), &args.board]
+            .concat()
+            .try_into()
+            .map_err(|_| invalid())?;
+
+        if all_cards.iter().collect::<CardSet>().len() != 7 {
+            return Err(invalid());
+        }
+
+        let made_hand = MadeHand::from(all_cards);
+
+        table.push(vec![
+            Value::Text(cards.iter().map(Card::to_string).collect()),
+            Value::Text(made_hand_type_name(made_hand.hand_type()).to_string()),
+            Value::Integer(made_hand.power_index() as u64),
+        ]);
+    }
+
+    Ok(table)
+}
+
+pub fn convert(args: &Args) -> Result<Table, CommandError> {
+    let input = match args.arguments.as_slice() {
+        [argument] if argument == "-" => {
+            let mut input = String::new();
+
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(CommandError::Io)?;
+
+            input
+        }
+        [argument] => argument.clone(),
+        _ => return Err(CommandError::MissingArgument("a range")),
+    };
+    let hand_range = match args.from {
+        Some(format) => HandRange::from_range_format(&input, format)
+            .map_err(CommandError::InvalidRangeFormat)?,
+        None => HandRange::from_str(input.trim()).map_err(CommandError::InvalidRange)?,
+    };
+    let mut table = Table::new(&["range"]);
+
+    table.push(vec![Value::Text(match args.to {
+        Some(format) => hand_range
+            .to_range_format(format)
+            .map_err(CommandError::UnwritableRange)?,
+        None => hand_range.to_string(),
+    })]);
+
+    Ok(table)
+}
+
+fn parse_ranges(values: &[String]) -> Result<Vec<HandRange>, CommandError> {
+    values
+        .iter()
+        .map(|value| HandRange::from_str(value).map_err(CommandError::InvalidRange))
+        .collect()
+}
+
+fn made_hand_type_name(hand_type: MadeHandType) -> &'static str {
+    match hand_type {
+        MadeHandType::StraightFlush => "straight flush",
+        MadeHandType::Quads => "quads",
+        MadeHandType::FullHouse => "full house",
+        MadeHandType::Flush => "flush",
+        MadeHandType::Straight => "straight",
+        MadeHandType::Trips => "trips",
+        MadeHandType::TwoPair => "two pair",
+        MadeHandType::Pair => "pair",
+        MadeHandType::HighCard => "high card",
+    }
+}
+
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+#[derive(Debug)]
+pub enum CommandError {
+    MissingArgument(&'static str),
+    InvalidRange(ParseHandRangeError),
+    InvalidRangeFormat(ParseRangeFormatError),
+    UnwritableRange(WriteRangeFormatError),
+    InvalidProbability(InvalidHandRangeError),
+    InvalidCards(String),
+    ExhaustiveWithoutFlop,
+    NoDeal,
+    Io(std::io::Error),
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CommandError::MissingArgument(argument) => write!(f, "{} must be given.", argument),
+            CommandError::InvalidRange(error) => error.fmt(f),
+            CommandError::InvalidRangeFormat(error) => error.fmt(f),
+            CommandError::UnwritableRange(error) => error.fmt(f),
+            CommandError::InvalidProbability(error) => error.fmt(f),
+            CommandError::InvalidCards(cards) => {
+                write!(f, "{} are not seven distinct cards with the board.", cards)
+            }
+            CommandError::ExhaustiveWithoutFlop => {
+                write!(f, "exhaustive evaluation needs at least a flop.")
+            }
+            CommandError::NoDeal => write!(f, "no combination of the ranges can be dealt."),
+            CommandError::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::OutputFormat;
+
+    fn run(
+        command: fn(&Args) -> Result<Table, CommandError>,
+        args: &[&str],
+    ) -> Result<String, CommandError> {
+        let args = Args::parse(args.iter().map(|arg| arg.to_string())).unwrap();
+
+        command(&args).map(|table| table.render(OutputFormat::Csv))
+    }
+
+    mod equity {
+        use super::*;
+
+        #[test]
+        fn it_evaluates_exhaustively_on_flop() {
+            assert_eq!(
+                run(equity, &["equity", "AsAh", "KsKh", "-b", "Kd8d2h"]).unwrap(),
+                "player,range,equity\n1,AsAh,0.0859\n2,KsKh,0.9141\n"
+            );
+        }
+
+        #[test]
+        fn it_evaluates_monte_carlo_preflop() {
+            let output = run(
+                equity,
+                &["equity", "AA", "KK", "-n", "2000", "--seed", "1", "-t", "2"],
+            )
+            .unwrap();
+
+            assert_eq!(
+                output,
+                run(
+                    equity,
+                    &["equity", "AA", "KK", "-n", "2000", "--seed", "1", "-t", "2"]
+                )
+                .unwrap()
+            );
+            assert!(output.starts_with("player,range,equity\n1,AA,0.8"));
+        }
+
+        #[test]
+        fn it_returns_error_when_a_range_is_missing() {
+            assert!(matches!(
+                run(equity, &["equity", "AA"]),
+                Err(CommandError::MissingArgument(_))
+            ));
+            assert!(matches!(
+                run(equity, &["equity", "AA", "KQx"]),
+                Err(CommandError::InvalidRange(_))
+            ));
+        }
+
+        #[test]
+        fn it_returns_error_when_nothing_can_be_dealt() {
+            assert!(matches!(
+                run(equity, &["equity", "AsAh", "AsKs", "-n", "10"]),
+                Err(CommandError::NoDeal)
+            ));
+        }
+
+        #[test]
+        fn it_returns_error_when_exhaustive_without_flop() {
+            assert!(matches!(
+                run(equity, &["equity", "AA", "KK", "--exhaustive"]),
+                Err(CommandError::ExhaustiveWithoutFlop)
+            ));
+        }
+    }
+
+    mod stats {
+        use super::*;
+
+        #[test]
+        fn it_counts_combos() {
+            assert_eq!(
+                run(stats, &["stats", "QQ+,AKs", "-d", "As"]).unwrap(),
+                "range,combos,percent,available combos\n\"QQ+,AKs\",22.00,1.66,18.00\n"
+            );
+        }
+
+        #[test]
+        fn it_counts_made_hand_types_on_full_board() {
+            let output = run(stats, &["stats", "KK,AKs", "-b", "Ks8d2h7c3s"]).unwrap();
+
+            assert_eq!(
+                output,
+                "range,combos,percent,available combos,straight flush,quads,full house,flush,straight,trips,two pair,pair,high card\n\
+                 \"KK,AKs\",10.00,0.75,6.00,0.00,0.00,0.00,0.00,0.00,3.00,0.00,3.00,0.00\n"
+            );
+        }
+
+        #[test]
+        fn it_accepts_ranks_in_either_order() {
+            assert_eq!(
+                run(stats, &["stats", "22-AA"]).unwrap(),
+                run(stats, &["stats", "AA-22"]).unwrap()
+            );
+        }
+    }
+
+    mod evaluate {
+        use super::*;
+
+        #[test]
+        fn it_evaluates_hole_cards_on_board() {
+            assert_eq!(
+                run(evaluate, &["evaluate", "AsKs", "7h7d", "-b", "QsJsTs2h2c"]).unwrap(),
+                "cards,hand,power index\nAsKs,straight flush,1\n7h7d,two pair,3207\n"
+            );
+        }
+
+        #[test]
+        fn it_evaluates_seven_cards() {
+            assert_eq!(
+                run(evaluate, &["evaluate", "AsAhAdAcKsKhKd"]).unwrap(),
+                "cards,hand,power index\nAsAhAdAcKsKhKd,quads,11\n"
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_cards_are_not_seven() {
+            assert!(matches!(
+                run(evaluate, &["evaluate", "AsKs", "-b", "QsJsTs"]),
+                Err(CommandError::InvalidCards(_))
+            ));
+            assert!(matches!(
+                run(evaluate, &["evaluate", "AsQs", "-b", "QsJsTs2h2c"]),
+                Err(CommandError::InvalidCards(_))
+            ));
+        }
+    }
+
+    mod convert {
+        use super::*;
+
+        #[test]
+        fn it_converts_between_range_formats() {
+            assert_eq!(
+                run(convert, &["convert", "QQ+,AKs:0.5", "--to", "gtoplus"]).unwrap(),
+                "range\n\"AA,KK,QQ,[50]AKs[/50]\"\n"
+            );
+            assert_eq!(
+                run(
+                    convert,
+                    &["convert", "AA,[50]AKs[/50]", "--from", "gtoplus"]
+                )
+                .unwrap(),
+                "range\n\"AA,AKs:0.5\"\n"
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_format_does_not_support_weights() {
+            assert!(matches!(
+                run(convert, &["convert", "QQ+,AKs:0.5", "--to", "flopzilla"]),
+                Err(CommandError::UnwritableRange(_))
+            ));
+        }
+    }
+}
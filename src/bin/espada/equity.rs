@@ -0,0 +1,383 @@
+use crate::commands::CommandError;
+use espada::card::{Card, CardSet};
+use espada::evaluator::{EquityAccumulator, FlopExhaustiveEvaluator, Showdown};
+use espada::hand_range::{HandRange, HandRangeSampler};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// the turn and the river as indexes of the deck without the flop, the same as the ones given to
+// `FlopExhaustiveEvaluator::scope()`
+type Runout = (u8, u8);
+
+// the runout dealt after the given one. (48, 49) is the end of the deck.
+fn next_runout((turn, river): Runout) -> Runout {
+    if river < 48 {
+        (turn, river + 1)
+    } else {
+        (turn + 1, turn + 2)
+    }
+}
+
+// runouts that complete the board without dealing any of the dead cards
+fn runouts(board: &[Card], dead_cards: &CardSet) -> Vec<Runout> {
+    let flop: CardSet = board[..3].iter().collect();
+    let deck = (!flop).into_iter().collect::<Vec<Card>>();
+    let dealt: CardSet = board[3..].iter().collect();
+    let mut runouts = vec![];
+
+    for turn in 0..48 {
+        for river in turn + 1..49 {
+            let cards = CardSet::from([deck[turn as usize], deck[river as usize]]);
+
+            if dealt.is_subset(&cards) && cards.is_disjoint(dead_cards) {
+                runouts.push((turn, river));
+            }
+        }
+    }
+
+    runouts
+}
+
+// consecutive runouts are merged into a scope from the first one to the one after the last
+fn scopes(runouts: &[Runout]) -> Vec<(Runout, Runout)> {
+    let mut scopes: Vec<(Runout, Runout)> = vec![];
+
+    for runout in runouts {
+        match scopes.last_mut() {
+            Some((_, end)) if end == runout => *end = next_runout(*runout),
+            _ => scopes.push((*runout, next_runout(*runout))),
+        }
+    }
+
+    scopes
+}
+
+// every runout of the board is evaluated. the board needs at least a flop.
+pub fn exhaustive(
+    players: &[HandRange],
+    board: &[Card],
+    dead_cards: &CardSet,
+    threads: usize,
+) -> Result<EquityAccumulator, CommandError> {
+    if board.len() < 3 {
+        return Err(CommandError::ExhaustiveWithoutFlop);
+    }
+
+    let flop = [Some(board[0]), Some(board[1]), Some(board[2]), None, None];
+    let players = players
+        .iter()
+        .map(|player| player.excluding(dead_cards))
+        .collect::<Vec<HandRange>>();
+    let runouts = runouts(board, dead_cards);
+    let chunk_len = runouts.len().div_ceil(threads).max(1);
+
+    // ranges are validated once here instead of in every thread
+    FlopExhaustiveEvaluator::new(&flop, &players).map_err(CommandError::InvalidProbability)?;
+
+    let accumulators = std::thread::scope(|s| {
+        let handles = runouts
+            .chunks(chunk_len)
+            .map(|chunk| {
+                let players = &players;
+
+                s.spawn(move || {
+                    let mut accumulator = EquityAccumulator::new(players.len());
+
+                    for ((turn_from, river_from), (turn_to, river_to)) in scopes(chunk) {
+                        let mut evaluator = FlopExhaustiveEvaluator::new(&flop, players).unwrap();
+
+                        evaluator.scope(turn_from, river_from, turn_to, river_to);
+                        accumulator.merge(&evaluator.equities());
+                    }
+
+                    accumulator
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<EquityAccumulator>>()
+    });
+
+    Ok(merge(players.len(), &accumulators))
+}
+
+// card pairs are drawn in proportion to their weights and the rest of the board is dealt at
+// random. each thread draws from its own rng seeded by `seed` and the thread index, so that the
+// same seed and thread count always give the same result. an iteration whose card pairs can't be
+// drawn without overlapping is skipped, so the accumulator may have fewer showdowns than
+// `iterations`.
+pub fn monte_carlo(
+    players: &[HandRange],
+    board: &[Card],
+    dead_cards: &CardSet,
+    iterations: u64,
+    threads: usize,
+    seed: u64,
+) -> Result<EquityAccumulator, CommandError> {
+    for player in players {
+        player
+            .validate()
+            .map_err(CommandError::InvalidProbability)?;
+    }
+
+    let samplers = players
+        .iter()
+        .map(HandRange::sampler)
+        .collect::<Vec<HandRangeSampler>>();
+    let known_cards = board.iter().collect::<CardSet>() | *dead_cards;
+
+    // the runout is drawn until a live card comes up, so there must be enough of them left once
+    // the card pairs are dealt
+    if known_cards.len() + 2 * players.len() + 5 - board.len() > 52 {
+        return Err(CommandError::NoDeal);
+    }
+
+    let accumulators = std::thread::scope(|s| {
+        let handles = (0..threads as u64)
+            .map(|thread_index| {
+                let samplers = &samplers;
+                let len = iterations / threads as u64
+                    + u64::from(thread_index < iterations % threads as u64);
+
+                s.spawn(move || {
+                    let mut rng = ChaCha8Rng::seed_from_u64(seed.wrapping_add(thread_index));
+                    let mut accumulator = EquityAccumulator::new(samplers.len());
+                    let mut runout = [Card::from_index(0); 5];
+
+                    for _ in 0..len {
+                        let Some(card_pairs) =
+                            HandRangeSampler::sample_jointly(samplers, &known_cards, &mut rng)
+                        else {
+                            continue;
+                        };
+                        let mut used_cards = card_pairs
+                            .iter()
+                            .fold(known_cards, |cards, card_pair| cards | card_pair.into());
+
+                        runout[..board.len()].copy_from_slice(board);
+
+                        for card in &mut runout[board.len()..] {
+                            *card = loop {
+                                let card = Card::from_index(rng.gen_range(0..52));
+
+                                if used_cards.insert(card) {
+                                    break card;
+                                }
+                            };
+                        }
+
+                        accumulator.add(&Showdown::new(card_pairs, runout, 1.0).unwrap());
+                    }
+
+                    accumulator
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<EquityAccumulator>>()
+    });
+
+    Ok(merge(players.len(), &accumulators))
+}
+
+fn merge(player_len: usize, accumulators: &[EquityAccumulator]) -> EquityAccumulator {
+    let mut merged = EquityAccumulator::new(player_len);
+
+    for accumulator in accumulators {
+        merged.merge(accumulator);
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use espada::card::parse_cards;
+    use std::str::FromStr;
+
+    fn cards(value: &str) -> Vec<Card> {
+        parse_cards(value)
+            .unwrap()
+            .into_iter()
+            .map(Option::unwrap)
+            .collect()
+    }
+
+    fn ranges(values: &[&str]) -> Vec<HandRange> {
+        values
+            .iter()
+            .map(|value| HandRange::from_str(value).unwrap())
+            .collect()
+    }
+
+    mod scopes {
+        use super::*;
+
+        #[test]
+        fn it_merges_consecutive_runouts() {
+            assert_eq!(
+                scopes(&[(0, 1), (0, 2), (0, 48), (1, 2), (1, 4), (47, 48)]),
+                vec![
+                    ((0, 1), (0, 3)),
+                    ((0, 48), (1, 3)),
+                    ((1, 4), (1, 5)),
+                    ((47, 48), (48, 49))
+                ]
+            );
+        }
+
+        #[test]
+        fn it_covers_every_runout_of_flop() {
+            let runouts = runouts(&cards("Ks8d2h"), &CardSet::empty());
+
+            assert_eq!(runouts.len(), 1176);
+            assert_eq!(scopes(&runouts), vec![((0, 1), (48, 49))]);
+        }
+
+        #[test]
+        fn it_keeps_runouts_with_dealt_cards_and_without_dead_cards() {
+            assert_eq!(runouts(&cards("Ks8d2h7c"), &CardSet::empty()).len(), 48);
+            assert_eq!(runouts(&cards("Ks8d2h7c3s"), &CardSet::empty()).len(), 1);
+            assert_eq!(
+                runouts(&cards("Ks8d2h"), &CardSet::from_str("AsAh").unwrap()).len(),
+                1081
+            );
+        }
+    }
+
+    mod exhaustive {
+        use super::*;
+
+        #[test]
+        fn it_evaluates_the_same_as_flop_exhaustive_evaluator() {
+            let board = cards("Ks8d2h");
+            let players = ranges(&["QQ+,AKs", "88-66,A8s+", "KQs,KJo"]);
+            let expected = FlopExhaustiveEvaluator::new(
+                &[Some(board[0]), Some(board[1]), Some(board[2]), None, None],
+                &players,
+            )
+            .unwrap()
+            .equities();
+            let accumulator = exhaustive(&players, &board, &CardSet::empty(), 3).unwrap();
+
+            assert_eq!(accumulator.showdown_len(), expected.showdown_len());
+
+            for i in 0..players.len() {
+                assert_eq!(accumulator.exact_equity(i), expected.exact_equity(i));
+            }
+        }
+
+        #[test]
+        fn it_evaluates_river_board() {
+            let players = ranges(&["AsAh", "KsKh"]);
+            let accumulator =
+                exhaustive(&players, &cards("Kd8d2h7c3s"), &CardSet::empty(), 2).unwrap();
+
+            assert_eq!(accumulator.showdown_len(), 1);
+            assert_eq!(accumulator.equities(), vec![0.0, 1.0]);
+        }
+
+        #[test]
+        fn it_excludes_dead_cards() {
+            let players = ranges(&["AA", "KsKh"]);
+            let accumulator = exhaustive(
+                &players,
+                &cards("Qd8d2h7c"),
+                &CardSet::from_str("AsAhAd").unwrap(),
+                1,
+            )
+            .unwrap();
+
+            // every combo of pocket aces has a dead card
+            assert_eq!(accumulator.showdown_len(), 0);
+
+            let accumulator = exhaustive(
+                &players,
+                &cards("Qd8d2h7c"),
+                &CardSet::from_str("Kd").unwrap(),
+                1,
+            )
+            .unwrap();
+
+            assert_eq!(accumulator.showdown_len(), 6 * 43);
+        }
+
+        #[test]
+        fn it_returns_error_without_flop() {
+            assert!(matches!(
+                exhaustive(&ranges(&["AA", "KK"]), &cards("Ks8d"), &CardSet::empty(), 1),
+                Err(CommandError::ExhaustiveWithoutFlop)
+            ));
+        }
+    }
+
+    mod monte_carlo {
+        use super::*;
+
+        #[test]
+        fn it_converges_to_exhaustive_equity() {
+            let board = cards("Ks8d2h");
+            let players = ranges(&["QQ+,AKs", "88-66,A8s+"]);
+            let expected = exhaustive(&players, &board, &CardSet::empty(), 1)
+                .unwrap()
+                .equities();
+            let accumulator =
+                monte_carlo(&players, &board, &CardSet::empty(), 20_000, 2, 0).unwrap();
+
+            assert_eq!(accumulator.showdown_len(), 20_000);
+            assert!((accumulator.equity(0) - expected[0]).abs() < 0.01);
+        }
+
+        #[test]
+        fn it_is_deterministic_for_the_same_seed() {
+            let players = ranges(&["QQ+,AKs", "22+"]);
+            let equities = |seed| {
+                monte_carlo(&players, &[], &CardSet::empty(), 1000, 2, seed)
+                    .unwrap()
+                    .equities()
+            };
+
+            assert_eq!(equities(7), equities(7));
+            assert_ne!(equities(7), equities(8));
+        }
+
+        #[test]
+        fn it_never_deals_dead_cards() {
+            let players = ranges(&["AsAh", "QsQh"]);
+            // 2c is the only card left for the river
+            let dead_cards = !CardSet::from_str("Ks8d2h7cAsAhQsQh2c").unwrap();
+            let accumulator =
+                monte_carlo(&players, &cards("Ks8d2h7c"), &dead_cards, 100, 1, 0).unwrap();
+
+            assert_eq!(accumulator.equities(), vec![1.0, 0.0]);
+        }
+
+        #[test]
+        fn it_returns_error_when_runout_can_not_be_dealt() {
+            let players = ranges(&["AsAh", "KsKh"]);
+            // 2h is the only live card once the card pairs are dealt
+            let dead_cards = !CardSet::from_str("AsAhKsKh2h").unwrap();
+
+            assert!(matches!(
+                monte_carlo(&players, &[], &dead_cards, 10, 1, 0),
+                Err(CommandError::NoDeal)
+            ));
+            assert!(monte_carlo(&players, &cards("3s4s5s6s"), &dead_cards, 10, 1, 0).is_ok());
+        }
+
+        #[test]
+        fn it_skips_iterations_when_nothing_can_be_dealt() {
+            let accumulator =
+                monte_carlo(&ranges(&["AsAh", "AsKs"]), &[], &CardSet::empty(), 10, 1, 0).unwrap();
+
+            assert_eq!(accumulator.showdown_len(), 0);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+mod args;
+mod commands;
+mod equity;
+mod output;
+
+use crate::args::{Args, Command, USAGE};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = match args.command {
+        Command::Equity => commands::equity(&args),
+        Command::Stats => commands::stats(&args),
+        Command::Evaluate => commands::evaluate(&args),
+        Command::Convert => commands::convert(&args),
+        Command::Help => {
+            print!("{}", USAGE);
+
+            return ExitCode::SUCCESS;
+        }
+    };
+
+    match result {
+        Ok(table) => {
+            print!("{}", table.render(args.format));
+
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+
+            ExitCode::FAILURE
+        }
+    }
+}
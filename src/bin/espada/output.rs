@@ -0,0 +1,258 @@
+use std::fmt::Write;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Text(String),
+    Integer(u64),
+    // the number of decimal places is kept so that every format prints the same digits
+    Float(f64, usize),
+}
+
+impl Value {
+    fn to_plain_string(&self) -> String {
+        match self {
+            Value::Text(text) => text.clone(),
+            Value::Integer(value) => value.to_string(),
+            Value::Float(value, precision) => format!("{:.*}", precision, value),
+        }
+    }
+}
+
+// rows of values under the same headers. every command prints its result as a table so that
+// the output format can be chosen independently from the command.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    pub fn new<S: ToString>(headers: &[S]) -> Table {
+        Table {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<Value>) {
+        debug_assert!(row.len() == self.headers.len());
+
+        self.rows.push(row);
+    }
+
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Table => self.render_table(),
+            OutputFormat::Json => self.render_json(),
+            OutputFormat::Csv => self.render_csv(),
+        }
+    }
+
+    // columns are aligned with spaces. numbers are aligned to the right.
+    fn render_table(&self) -> String {
+        let cells = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(Value::to_plain_string)
+                    .collect::<Vec<String>>()
+            })
+            .collect::<Vec<Vec<String>>>();
+        let widths = self
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([header.chars().count()])
+                    .max()
+                    .unwrap()
+            })
+            .collect::<Vec<usize>>();
+        let mut output = String::new();
+
+        let header_line = self
+            .headers
+            .iter()
+            .zip(&widths)
+            .map(|(header, width)| format!("{:<width$}", header, width = width))
+            .collect::<Vec<String>>()
+            .join("  ");
+
+        writeln!(output, "{}", header_line.trim_end()).unwrap();
+
+        for (row, values) in cells.iter().zip(&self.rows) {
+            let line = row
+                .iter()
+                .zip(values)
+                .zip(&widths)
+                .map(|((cell, value), width)| match value {
+                    Value::Text(_) => format!("{:<width$}", cell, width = width),
+                    _ => format!("{:>width$}", cell, width = width),
+                })
+                .collect::<Vec<String>>()
+                .join("  ");
+
+            writeln!(output, "{}", line.trim_end()).unwrap();
+        }
+
+        output
+    }
+
+    // an array of objects keyed by the headers
+    fn render_json(&self) -> String {
+        let objects = self
+            .rows
+            .iter()
+            .map(|row| {
+                let fields = self
+                    .headers
+                    .iter()
+                    .zip(row)
+                    .map(|(header, value)| {
+                        let value = match value {
+                            Value::Text(text) => json_string(text),
+                            Value::Float(value, _) if !value.is_finite() => "null".to_string(),
+                            _ => value.to_plain_string(),
+                        };
+
+                        format!("{}:{}", json_string(header), value)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                format!("{{{}}}", fields)
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+
+        format!("[{}]\n", objects)
+    }
+
+    fn render_csv(&self) -> String {
+        let mut output = String::new();
+
+        for row in [self.headers.clone()]
+            .into_iter()
+            .chain(self.rows.iter().map(|row| {
+                row.iter()
+                    .map(Value::to_plain_string)
+                    .collect::<Vec<String>>()
+            }))
+        {
+            let line = row
+                .iter()
+                .map(|cell| csv_field(cell))
+                .collect::<Vec<String>>()
+                .join(",");
+
+            writeln!(output, "{}", line).unwrap();
+        }
+
+        output
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+
+    escaped
+}
+
+// fields are quoted only when they contain a separator, a quote or a line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        let mut table = Table::new(&["range", "combos", "equity"]);
+
+        table.push(vec![
+            Value::Text("QQ+,AKs".to_string()),
+            Value::Integer(22),
+            Value::Float(0.61234, 4),
+        ]);
+        table.push(vec![
+            Value::Text("22+".to_string()),
+            Value::Integer(78),
+            Value::Float(0.38766, 4),
+        ]);
+
+        table
+    }
+
+    mod render {
+        use super::*;
+
+        #[test]
+        fn it_renders_aligned_table() {
+            assert_eq!(
+                table().render(OutputFormat::Table),
+                "range    combos  equity\nQQ+,AKs      22  0.6123\n22+          78  0.3877\n"
+            );
+        }
+
+        #[test]
+        fn it_renders_json() {
+            assert_eq!(
+                table().render(OutputFormat::Json),
+                "[{\"range\":\"QQ+,AKs\",\"combos\":22,\"equity\":0.6123},{\"range\":\"22+\",\"combos\":78,\"equity\":0.3877}]\n"
+            );
+        }
+
+        #[test]
+        fn it_renders_csv() {
+            assert_eq!(
+                table().render(OutputFormat::Csv),
+                "range,combos,equity\n\"QQ+,AKs\",22,0.6123\n22+,78,0.3877\n"
+            );
+        }
+
+        #[test]
+        fn it_escapes_json_strings() {
+            let mut table = Table::new(&["value"]);
+
+            table.push(vec![Value::Text("a\"b\\c\n".to_string())]);
+            table.push(vec![Value::Float(f64::NAN, 2)]);
+
+            assert_eq!(
+                table.render(OutputFormat::Json),
+                "[{\"value\":\"a\\\"b\\\\c\\n\"},{\"value\":null}]\n"
+            );
+        }
+    }
+}
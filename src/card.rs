@@ -1,10 +1,18 @@
+#[allow(clippy::module_inception)]
 mod card;
+mod card_set;
+mod deck;
+mod notation;
 mod rank;
 mod rank_range;
 mod suit;
 mod suit_range;
 
 pub use card::{Card, ParseCardError};
+pub use card_set::{CardSet, CardSetIter, ParseCardSetError};
+pub use deck::Deck;
+pub use notation::{parse_board, parse_cards, Notate, Notated, Notation};
+pub(crate) use notation::{tokenize_cards, CardToken};
 pub use rank::Rank;
 pub use rank_range::RankRange;
 pub use suit::Suit;
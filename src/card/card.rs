@@ -1,100 +1,74 @@
+use super::rank_range::RANKS;
+use super::suit_range::SUITS;
 use super::{Rank, Suit};
-use std::fmt::{Debug, Display, Formatter};
-use std::str::FromStr;
+use alloc::string::{String, ToString};
+use core::fmt::{Debug, Display, Formatter};
+use core::str::FromStr;
 
 #[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
 pub struct Card(Rank, Suit);
 
 impl Card {
-    pub fn new(rank: Rank, suit: Suit) -> Self {
+    pub const fn new(rank: Rank, suit: Suit) -> Self {
         Card(rank, suit)
     }
 
-    pub fn rank(&self) -> &Rank {
+    pub const fn rank(&self) -> &Rank {
         &self.0
     }
 
-    pub fn suit(&self) -> &Suit {
+    pub const fn suit(&self) -> &Suit {
         &self.1
     }
+
+    // index in 0..52 ordered the same way as `Card`'s `Ord` and `u64` bit position, i.e.
+    // As = 0, Ah = 1, Ad = 2, Ac = 3, Ks = 4, ..., 2c = 51.
+    pub const fn index(&self) -> u8 {
+        self.0 as u8 * 4 + self.1 as u8
+    }
+
+    pub const fn from_index(index: u8) -> Card {
+        assert!(index < 52, "card index out of range.");
+
+        CARDS[index as usize]
+    }
+
+    pub const fn all() -> [Card; 52] {
+        CARDS
+    }
 }
 
 impl Display for Card {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}{}", self.rank(), self.suit())
     }
 }
 
 impl Debug for Card {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "Card({}{})", self.rank(), self.suit())
     }
 }
 
-const SPADE_MASK: u64 = 0b0001000100010001000100010001000100010001000100010001;
-const HEART_MASK: u64 = 0b0010001000100010001000100010001000100010001000100010;
-const DIAMOND_MASK: u64 = 0b0100010001000100010001000100010001000100010001000100;
-const CLUB_MASK: u64 = 0b1000100010001000100010001000100010001000100010001000;
-
-const ACE_MASK: u64 = 0b0000000000000000000000000000000000000000000000001111;
-const KING_MASK: u64 = 0b0000000000000000000000000000000000000000000011110000;
-const QUEEN_MASK: u64 = 0b0000000000000000000000000000000000000000111100000000;
-const JACK_MASK: u64 = 0b0000000000000000000000000000000000001111000000000000;
-const TEN_MASK: u64 = 0b0000000000000000000000000000000011110000000000000000;
-const NINE_MASK: u64 = 0b0000000000000000000000000000111100000000000000000000;
-const EIGHT_MASK: u64 = 0b0000000000000000000000001111000000000000000000000000;
-const SEVEN_MASK: u64 = 0b0000000000000000000011110000000000000000000000000000;
-const SIX_MASK: u64 = 0b0000000000000000111100000000000000000000000000000000;
-const FIVE_MASK: u64 = 0b0000000000001111000000000000000000000000000000000000;
-const FOUR_MASK: u64 = 0b0000000011110000000000000000000000000000000000000000;
-const TREY_MASK: u64 = 0b0000111100000000000000000000000000000000000000000000;
-const DEUCE_MASK: u64 = 0b1111000000000000000000000000000000000000000000000000;
+const CARDS: [Card; 52] = {
+    let mut cards = [Card(Rank::Ace, Suit::Spade); 52];
+    let mut i = 0;
+
+    while i < 52 {
+        cards[i] = Card(RANKS[i / 4], SUITS[i % 4]);
+        i += 1;
+    }
+
+    cards
+};
 
 impl From<&u64> for Card {
     fn from(value: &u64) -> Self {
-        let suit = if value & SPADE_MASK >= 1 {
-            Suit::Spade
-        } else if value & HEART_MASK >= 1 {
-            Suit::Heart
-        } else if value & DIAMOND_MASK >= 1 {
-            Suit::Diamond
-        } else if value & CLUB_MASK >= 1 {
-            Suit::Club
-        } else {
+        if *value == 0 || value.trailing_zeros() >= 52 {
             panic!();
-        };
-
-        let rank = if value & ACE_MASK >= 1 {
-            Rank::Ace
-        } else if value & KING_MASK >= 1 {
-            Rank::King
-        } else if value & QUEEN_MASK >= 1 {
-            Rank::Queen
-        } else if value & JACK_MASK >= 1 {
-            Rank::Jack
-        } else if value & TEN_MASK >= 1 {
-            Rank::Ten
-        } else if value & NINE_MASK >= 1 {
-            Rank::Nine
-        } else if value & EIGHT_MASK >= 1 {
-            Rank::Eight
-        } else if value & SEVEN_MASK >= 1 {
-            Rank::Seven
-        } else if value & SIX_MASK >= 1 {
-            Rank::Six
-        } else if value & FIVE_MASK >= 1 {
-            Rank::Five
-        } else if value & FOUR_MASK >= 1 {
-            Rank::Four
-        } else if value & TREY_MASK >= 1 {
-            Rank::Trey
-        } else if value & DEUCE_MASK >= 1 {
-            Rank::Deuce
-        } else {
-            panic!();
-        };
+        }
 
-        Card::new(rank, suit)
+        Card::from_index(value.trailing_zeros() as u8)
     }
 }
 
@@ -108,10 +82,17 @@ impl FromStr for Card {
     type Err = ParseCardError;
 
     fn from_str(v: &str) -> Result<Self, Self::Err> {
-        if v.len() == 2 {
-            if let (Ok(rank), Ok(suit)) = (Rank::from_str(&v[0..1]), Suit::from_str(&v[1..2])) {
-                return Ok(Card(rank, suit));
-            };
+        let mut chars = v.chars();
+
+        if let Some(suit_char) = chars.next_back() {
+            let rank_str = chars.as_str();
+
+            if rank_str == "10" || rank_str.chars().count() == 1 {
+                if let (Ok(rank), Ok(suit)) = (Rank::from_str(rank_str), Suit::try_from(suit_char))
+                {
+                    return Ok(Card(rank, suit));
+                };
+            }
         };
 
         Err(ParseCardError(v.to_string()))
@@ -119,40 +100,17 @@ impl FromStr for Card {
 }
 
 #[derive(Debug)]
-pub struct ParseCardError(String);
+pub struct ParseCardError(pub(crate) String);
 
 impl Display for ParseCardError {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{} is not a valid string for a card.", self.0)
     }
 }
 
 impl From<&Card> for u64 {
     fn from(card: &Card) -> Self {
-        let rank_bit = match card.0 {
-            Rank::Ace => ACE_MASK,
-            Rank::King => KING_MASK,
-            Rank::Queen => QUEEN_MASK,
-            Rank::Jack => JACK_MASK,
-            Rank::Ten => TEN_MASK,
-            Rank::Nine => NINE_MASK,
-            Rank::Eight => EIGHT_MASK,
-            Rank::Seven => SEVEN_MASK,
-            Rank::Six => SIX_MASK,
-            Rank::Five => FIVE_MASK,
-            Rank::Four => FOUR_MASK,
-            Rank::Trey => TREY_MASK,
-            Rank::Deuce => DEUCE_MASK,
-        };
-
-        let suit_bit = match card.1 {
-            Suit::Spade => SPADE_MASK,
-            Suit::Heart => HEART_MASK,
-            Suit::Diamond => DIAMOND_MASK,
-            Suit::Club => CLUB_MASK,
-        };
-
-        rank_bit & suit_bit
+        1 << card.index()
     }
 }
 
@@ -475,6 +433,72 @@ mod tests {
         }
     }
 
+    mod index {
+        use super::*;
+
+        #[test]
+        fn it_returns_index() {
+            assert_eq!(Card::new(Rank::Ace, Suit::Spade).index(), 0);
+            assert_eq!(Card::new(Rank::Ace, Suit::Club).index(), 3);
+            assert_eq!(Card::new(Rank::King, Suit::Heart).index(), 5);
+            assert_eq!(Card::new(Rank::Ten, Suit::Diamond).index(), 18);
+            assert_eq!(Card::new(Rank::Deuce, Suit::Club).index(), 51);
+        }
+
+        #[test]
+        fn it_matches_u64_bit_position() {
+            for card in Card::all() {
+                assert_eq!(u64::from(card).trailing_zeros(), card.index() as u32);
+            }
+        }
+    }
+
+    mod from_index {
+        use super::*;
+
+        #[test]
+        fn it_returns_card() {
+            assert_eq!(Card::from_index(0), Card::new(Rank::Ace, Suit::Spade));
+            assert_eq!(Card::from_index(5), Card::new(Rank::King, Suit::Heart));
+            assert_eq!(Card::from_index(51), Card::new(Rank::Deuce, Suit::Club));
+        }
+
+        #[test]
+        fn it_round_trips_with_index() {
+            for index in 0..52 {
+                assert_eq!(Card::from_index(index).index(), index);
+            }
+        }
+
+        #[test]
+        fn it_is_usable_in_const_context() {
+            const KING_HEART: Card = Card::from_index(5);
+
+            assert_eq!(KING_HEART, Card::new(Rank::King, Suit::Heart));
+        }
+
+        #[test]
+        #[should_panic]
+        fn it_panics_when_out_of_range() {
+            Card::from_index(52);
+        }
+    }
+
+    mod all {
+        use super::*;
+
+        #[test]
+        fn it_returns_all_cards_in_order() {
+            let cards = Card::all();
+
+            assert_eq!(cards[0], Card::new(Rank::Ace, Suit::Spade));
+            assert_eq!(cards[1], Card::new(Rank::Ace, Suit::Heart));
+            assert_eq!(cards[4], Card::new(Rank::King, Suit::Spade));
+            assert_eq!(cards[51], Card::new(Rank::Deuce, Suit::Club));
+            assert!(cards.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+
     mod card_from_u64 {
         use super::*;
 
@@ -595,6 +619,47 @@ mod tests {
         }
     }
 
+    mod card_from_alternate_str {
+        use super::*;
+
+        #[test]
+        fn it_parses_10_as_ten() {
+            assert_eq!(
+                Card::from_str("10h").unwrap(),
+                Card::new(Rank::Ten, Suit::Heart)
+            );
+        }
+
+        #[test]
+        fn it_parses_unicode_suit() {
+            assert_eq!(
+                Card::from_str("A♠").unwrap(),
+                Card::new(Rank::Ace, Suit::Spade)
+            );
+            assert_eq!(
+                Card::from_str("10♦").unwrap(),
+                Card::new(Rank::Ten, Suit::Diamond)
+            );
+        }
+
+        #[test]
+        fn it_parses_uppercase_suit() {
+            assert_eq!(
+                Card::from_str("QC").unwrap(),
+                Card::new(Rank::Queen, Suit::Club)
+            );
+        }
+
+        #[test]
+        fn it_fails_parsing_invalid_str() {
+            assert!(Card::from_str("").is_err());
+            assert!(Card::from_str("A").is_err());
+            assert!(Card::from_str("11h").is_err());
+            assert!(Card::from_str("Asx").is_err());
+            assert!(Card::from_str("xx").is_err());
+        }
+    }
+
     mod u64_from_card {
         use super::*;
 
@@ -0,0 +1,458 @@
+use super::Card;
+use alloc::string::{String, ToString};
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{BitAnd, BitOr, Not, Sub};
+use core::str::FromStr;
+
+const ALL_CARDS_MASK: u64 = 0b1111111111111111111111111111111111111111111111111111;
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub fn empty() -> Self {
+        CardSet(0)
+    }
+
+    pub fn full() -> Self {
+        CardSet(ALL_CARDS_MASK)
+    }
+
+    pub fn insert(&mut self, card: Card) -> bool {
+        let bit = u64::from(&card);
+        let is_new = self.0 & bit == 0;
+
+        self.0 |= bit;
+
+        is_new
+    }
+
+    pub fn remove(&mut self, card: &Card) -> bool {
+        let bit = u64::from(card);
+        let is_present = self.0 & bit != 0;
+
+        self.0 &= !bit;
+
+        is_present
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.0 & u64::from(card) != 0
+    }
+
+    pub fn union(&self, other: &CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: &CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    pub fn difference(&self, other: &CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    pub fn is_disjoint(&self, other: &CardSet) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    pub fn is_subset(&self, other: &CardSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+}
+
+impl From<u64> for CardSet {
+    fn from(value: u64) -> Self {
+        CardSet(value & ALL_CARDS_MASK)
+    }
+}
+
+impl From<&CardSet> for u64 {
+    fn from(card_set: &CardSet) -> Self {
+        card_set.0
+    }
+}
+
+impl From<CardSet> for u64 {
+    fn from(card_set: CardSet) -> Self {
+        card_set.0
+    }
+}
+
+impl From<&Card> for CardSet {
+    fn from(card: &Card) -> Self {
+        CardSet(u64::from(card))
+    }
+}
+
+impl From<Card> for CardSet {
+    fn from(card: Card) -> Self {
+        CardSet::from(&card)
+    }
+}
+
+impl<const N: usize> From<[Card; N]> for CardSet {
+    fn from(cards: [Card; N]) -> Self {
+        cards.into_iter().collect()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<T: IntoIterator<Item = Card>>(iter: T) -> Self {
+        let mut card_set = CardSet::empty();
+
+        for card in iter {
+            card_set.insert(card);
+        }
+
+        card_set
+    }
+}
+
+impl<'a> FromIterator<&'a Card> for CardSet {
+    fn from_iter<T: IntoIterator<Item = &'a Card>>(iter: T) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<T: IntoIterator<Item = Card>>(&mut self, iter: T) {
+        for card in iter {
+            self.insert(card);
+        }
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(&rhs)
+    }
+}
+
+impl Not for CardSet {
+    type Output = CardSet;
+
+    fn not(self) -> Self::Output {
+        CardSet(!self.0 & ALL_CARDS_MASK)
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let bit = self.0 & self.0.wrapping_neg();
+
+        self.0 ^= bit;
+
+        Some(Card::from(bit))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl Display for CardSet {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        for card in self.iter() {
+            Display::fmt(&card, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for CardSet {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "CardSet({})", self)
+    }
+}
+
+impl FromStr for CardSet {
+    type Err = ParseCardSetError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.replace(' ', "");
+
+        if !trimmed.len().is_multiple_of(2) {
+            return Err(Self::Err::InvalidLength(trimmed.len()));
+        }
+
+        let mut card_set = CardSet::empty();
+
+        for i in 0..trimmed.len() / 2 {
+            let card_str = trimmed
+                .get(i * 2..i * 2 + 2)
+                .ok_or_else(|| Self::Err::InvalidCardStr(trimmed.clone()))?;
+            let card = Card::from_str(card_str)
+                .map_err(|_| Self::Err::InvalidCardStr(card_str.to_string()))?;
+
+            if !card_set.insert(card) {
+                return Err(Self::Err::DuplicatedCard(card));
+            }
+        }
+
+        Ok(card_set)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseCardSetError {
+    InvalidLength(usize),
+    InvalidCardStr(String),
+    DuplicatedCard(Card),
+}
+
+impl Display for ParseCardSetError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            ParseCardSetError::InvalidLength(len) => {
+                write!(f, "{} is not a valid length for a card set string.", len)
+            }
+            ParseCardSetError::InvalidCardStr(value) => {
+                write!(f, "{} is not a valid string for a card.", value)
+            }
+            ParseCardSetError::DuplicatedCard(card) => {
+                write!(f, "{} appears more than once.", card)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{Rank, Suit};
+
+    mod insert {
+        use super::*;
+
+        #[test]
+        fn it_inserts_cards() {
+            let mut card_set = CardSet::empty();
+
+            assert!(card_set.insert(Card::new(Rank::Ace, Suit::Spade)));
+            assert!(card_set.insert(Card::new(Rank::Deuce, Suit::Club)));
+            assert!(!card_set.insert(Card::new(Rank::Ace, Suit::Spade)));
+            assert_eq!(card_set.len(), 2);
+        }
+    }
+
+    mod remove {
+        use super::*;
+
+        #[test]
+        fn it_removes_cards() {
+            let mut card_set: CardSet = "AsKd7c".parse().unwrap();
+
+            assert!(card_set.remove(&Card::new(Rank::King, Suit::Diamond)));
+            assert!(!card_set.remove(&Card::new(Rank::King, Suit::Diamond)));
+            assert_eq!(card_set, "As7c".parse().unwrap());
+        }
+    }
+
+    mod contains {
+        use super::*;
+
+        #[test]
+        fn it_contains_inserted_cards_only() {
+            let card_set: CardSet = "AsKd7c".parse().unwrap();
+
+            assert!(card_set.contains(&Card::new(Rank::Ace, Suit::Spade)));
+            assert!(card_set.contains(&Card::new(Rank::King, Suit::Diamond)));
+            assert!(card_set.contains(&Card::new(Rank::Seven, Suit::Club)));
+            assert!(!card_set.contains(&Card::new(Rank::Ace, Suit::Heart)));
+            assert!(!card_set.contains(&Card::new(Rank::Seven, Suit::Spade)));
+        }
+    }
+
+    mod set_operations {
+        use super::*;
+
+        #[test]
+        fn it_unions() {
+            let left: CardSet = "AsKd".parse().unwrap();
+            let right: CardSet = "Kd7c".parse().unwrap();
+
+            assert_eq!(left.union(&right), "AsKd7c".parse().unwrap());
+            assert_eq!(left | right, "AsKd7c".parse().unwrap());
+        }
+
+        #[test]
+        fn it_intersects() {
+            let left: CardSet = "AsKd".parse().unwrap();
+            let right: CardSet = "Kd7c".parse().unwrap();
+
+            assert_eq!(left.intersection(&right), "Kd".parse().unwrap());
+            assert_eq!(left & right, "Kd".parse().unwrap());
+        }
+
+        #[test]
+        fn it_differs() {
+            let left: CardSet = "AsKd".parse().unwrap();
+            let right: CardSet = "Kd7c".parse().unwrap();
+
+            assert_eq!(left.difference(&right), "As".parse().unwrap());
+            assert_eq!(left - right, "As".parse().unwrap());
+        }
+
+        #[test]
+        fn it_complements_within_52_cards() {
+            assert_eq!((!CardSet::empty()).len(), 52);
+            assert_eq!(!CardSet::full(), CardSet::empty());
+        }
+
+        #[test]
+        fn it_checks_disjointness() {
+            let left: CardSet = "AsKd".parse().unwrap();
+
+            assert!(left.is_disjoint(&"7c2h".parse().unwrap()));
+            assert!(!left.is_disjoint(&"Kd2h".parse().unwrap()));
+        }
+    }
+
+    mod iter {
+        use super::*;
+
+        #[test]
+        fn it_iterates_in_card_order() {
+            let card_set: CardSet = "7cAs2dKd".parse().unwrap();
+
+            assert_eq!(
+                card_set.iter().collect::<Vec<_>>(),
+                vec![
+                    Card::new(Rank::Ace, Suit::Spade),
+                    Card::new(Rank::King, Suit::Diamond),
+                    Card::new(Rank::Seven, Suit::Club),
+                    Card::new(Rank::Deuce, Suit::Diamond),
+                ]
+            );
+        }
+
+        #[test]
+        fn it_iterates_all_cards() {
+            let cards = CardSet::full().iter().collect::<Vec<_>>();
+
+            assert_eq!(cards.len(), 52);
+            assert!(cards.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+
+    mod display {
+        use super::*;
+
+        #[test]
+        fn it_formats_cards() {
+            assert_eq!("Kd7cAs".parse::<CardSet>().unwrap().to_string(), "AsKd7c");
+        }
+
+        #[test]
+        fn it_formats_empty() {
+            assert_eq!(CardSet::empty().to_string(), "");
+        }
+    }
+
+    mod from_str {
+        use super::*;
+
+        #[test]
+        fn it_parses_str_into_card_set() {
+            assert_eq!(
+                CardSet::from_str("AsKd7c").unwrap(),
+                CardSet::from([
+                    Card::new(Rank::Ace, Suit::Spade),
+                    Card::new(Rank::King, Suit::Diamond),
+                    Card::new(Rank::Seven, Suit::Club),
+                ])
+            );
+        }
+
+        #[test]
+        fn it_parses_empty_str() {
+            assert_eq!(CardSet::from_str("").unwrap(), CardSet::empty());
+        }
+
+        #[test]
+        fn it_returns_error_when_invalid_length() {
+            assert_eq!(
+                CardSet::from_str("AsK").unwrap_err(),
+                ParseCardSetError::InvalidLength(3)
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_invalid_string() {
+            assert_eq!(
+                CardSet::from_str("AsKj").unwrap_err(),
+                ParseCardSetError::InvalidCardStr("Kj".to_string())
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_duplicated() {
+            assert_eq!(
+                CardSet::from_str("AsKdAs").unwrap_err(),
+                ParseCardSetError::DuplicatedCard(Card::new(Rank::Ace, Suit::Spade))
+            );
+        }
+    }
+}
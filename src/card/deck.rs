@@ -0,0 +1,334 @@
+use super::{Card, CardSet};
+use crate::hand_range::{CardPair, HandRange, HandRangeSampler};
+use alloc::vec::Vec;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Deck {
+    pub fn new() -> Deck {
+        Deck {
+            cards: Card::all().into(),
+        }
+    }
+
+    pub fn without(dead_cards: &CardSet) -> Deck {
+        Deck {
+            cards: (!*dead_cards).into_iter().collect(),
+        }
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn card_set(&self) -> CardSet {
+        self.cards.iter().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.cards.contains(card)
+    }
+
+    pub fn remove(&mut self, card: &Card) -> bool {
+        match self.cards.iter().position(|c| c == card) {
+            Some(index) => {
+                self.cards.remove(index);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove_all(&mut self, cards: &CardSet) {
+        self.cards.retain(|card| !cards.contains(card));
+    }
+
+    // Fisher-Yates shuffle. any rng implementing `rand::Rng` can be plugged in.
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        for i in (1..self.cards.len()).rev() {
+            let j = rng.gen_range(0..=i);
+
+            self.cards.swap(i, j);
+        }
+    }
+
+    // the same seed always produces the same order regardless of the platform.
+    pub fn shuffle_with_seed(&mut self, seed: u64) {
+        self.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+    }
+
+    pub fn deal(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    pub fn deal_n(&mut self, n: usize) -> Option<Vec<Card>> {
+        if n > self.cards.len() {
+            return None;
+        }
+
+        let mut dealt = self.cards.split_off(self.cards.len() - n);
+        dealt.reverse();
+
+        Some(dealt)
+    }
+
+    // draws a card pair from the cards left in the deck in proportion to its weight. returns
+    // `None` when every card pair is blocked or the weights can't be drawn from. the sampler of
+    // the range is built on every call, so `deal_from_sampler()` is preferred to deal repeatedly.
+    pub fn deal_from_hand_range<R: Rng + ?Sized>(
+        &mut self,
+        hand_range: &HandRange,
+        rng: &mut R,
+    ) -> Option<CardPair> {
+        self.deal_from_sampler(&hand_range.sampler(), rng)
+    }
+
+    // the same as `deal_from_hand_range()` with a sampler built once for the range
+    pub fn deal_from_sampler<R: Rng + ?Sized>(
+        &mut self,
+        sampler: &HandRangeSampler,
+        rng: &mut R,
+    ) -> Option<CardPair> {
+        let card_pair = sampler.sample_excluding(&!self.card_set(), rng)?;
+
+        self.remove(&card_pair[0]);
+        self.remove(&card_pair[1]);
+
+        Some(card_pair)
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new()
+    }
+}
+
+impl IntoIterator for Deck {
+    type Item = Card;
+    type IntoIter = alloc::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{Rank, Suit};
+    use std::str::FromStr;
+
+    mod new {
+        use super::*;
+
+        #[test]
+        fn it_contains_52_cards() {
+            let deck = Deck::new();
+
+            assert_eq!(deck.len(), 52);
+            assert_eq!(deck.card_set(), CardSet::full());
+        }
+
+        #[test]
+        fn it_contains_cards_except_dead_cards() {
+            let deck = Deck::without(&CardSet::from_str("AsKd7c").unwrap());
+
+            assert_eq!(deck.len(), 49);
+            assert!(!deck.contains(&Card::new(Rank::Ace, Suit::Spade)));
+            assert!(!deck.contains(&Card::new(Rank::King, Suit::Diamond)));
+            assert!(!deck.contains(&Card::new(Rank::Seven, Suit::Club)));
+        }
+    }
+
+    mod remove {
+        use super::*;
+
+        #[test]
+        fn it_removes_card() {
+            let mut deck = Deck::new();
+
+            assert!(deck.remove(&Card::new(Rank::Ace, Suit::Spade)));
+            assert!(!deck.remove(&Card::new(Rank::Ace, Suit::Spade)));
+            assert_eq!(deck.len(), 51);
+        }
+
+        #[test]
+        fn it_removes_all_cards() {
+            let mut deck = Deck::new();
+
+            deck.remove_all(&CardSet::from_str("AsKd7c").unwrap());
+
+            assert_eq!(deck, Deck::without(&CardSet::from_str("AsKd7c").unwrap()));
+        }
+    }
+
+    mod shuffle {
+        use super::*;
+
+        #[test]
+        fn it_keeps_all_cards() {
+            let mut deck = Deck::new();
+
+            deck.shuffle_with_seed(42);
+
+            assert_eq!(deck.len(), 52);
+            assert_eq!(deck.card_set(), CardSet::full());
+            assert_ne!(deck, Deck::new());
+        }
+
+        #[test]
+        fn it_shuffles_reproducibly_with_the_same_seed() {
+            let mut left = Deck::new();
+            let mut right = Deck::new();
+
+            left.shuffle_with_seed(12345);
+            right.shuffle_with_seed(12345);
+
+            assert_eq!(left, right);
+        }
+
+        #[test]
+        fn it_shuffles_differently_with_different_seeds() {
+            let mut left = Deck::new();
+            let mut right = Deck::new();
+
+            left.shuffle_with_seed(1);
+            right.shuffle_with_seed(2);
+
+            assert_ne!(left, right);
+        }
+
+        #[test]
+        fn it_shuffles_with_pluggable_rng() {
+            let mut left = Deck::new();
+            let mut right = Deck::new();
+
+            left.shuffle(&mut ChaCha8Rng::seed_from_u64(7));
+            right.shuffle_with_seed(7);
+
+            assert_eq!(left, right);
+        }
+    }
+
+    mod deal {
+        use super::*;
+
+        #[test]
+        fn it_deals_until_empty() {
+            let mut deck = Deck::without(&!CardSet::from_str("AsKd").unwrap());
+
+            assert_eq!(deck.deal(), Some(Card::new(Rank::King, Suit::Diamond)));
+            assert_eq!(deck.deal(), Some(Card::new(Rank::Ace, Suit::Spade)));
+            assert_eq!(deck.deal(), None);
+        }
+
+        #[test]
+        fn it_deals_n_cards() {
+            let mut deck = Deck::new();
+
+            deck.shuffle_with_seed(3);
+
+            let top = deck.cards()[49..].iter().rev().copied().collect::<Vec<_>>();
+
+            assert_eq!(deck.deal_n(3), Some(top));
+            assert_eq!(deck.len(), 49);
+            assert_eq!(deck.deal_n(50), None);
+            assert_eq!(deck.len(), 49);
+        }
+    }
+
+    mod deal_from_hand_range {
+        use super::*;
+
+        #[test]
+        fn it_deals_card_pair_without_collisions() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let hand_range = HandRange::from_str("AA,KK").unwrap();
+
+            for _ in 0..100 {
+                let mut deck = Deck::without(&CardSet::from_str("AsKs").unwrap());
+                let left = deck.deal_from_hand_range(&hand_range, &mut rng).unwrap();
+                let right = deck.deal_from_hand_range(&hand_range, &mut rng).unwrap();
+
+                assert!(CardSet::from(left).is_disjoint(&CardSet::from(right)));
+                assert!(!CardSet::from(left).contains(&Card::new(Rank::Ace, Suit::Spade)));
+                assert!(!CardSet::from(right).contains(&Card::new(Rank::King, Suit::Spade)));
+                assert_eq!(deck.len(), 46);
+            }
+        }
+
+        #[test]
+        fn it_deals_the_same_as_sampler() {
+            let hand_range = HandRange::from_str("AA,KK:0.5,AKs:0.25").unwrap();
+            let sampler = hand_range.sampler();
+            let mut left_rng = ChaCha8Rng::seed_from_u64(0);
+            let mut right_rng = ChaCha8Rng::seed_from_u64(0);
+            let mut left = Deck::without(&CardSet::from_str("Ad").unwrap());
+            let mut right = left.clone();
+
+            for _ in 0..10 {
+                assert_eq!(
+                    left.deal_from_hand_range(&hand_range, &mut left_rng),
+                    right.deal_from_sampler(&sampler, &mut right_rng)
+                );
+            }
+
+            assert_eq!(left, right);
+        }
+
+        #[test]
+        fn it_never_deals_card_pair_with_zero_probability() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let hand_range = HandRange::from_str("AA:0,KK:0.5").unwrap();
+
+            for _ in 0..100 {
+                let mut deck = Deck::new();
+                let card_pair = deck.deal_from_hand_range(&hand_range, &mut rng).unwrap();
+
+                assert_eq!(card_pair[0].rank(), &Rank::King);
+            }
+        }
+
+        #[test]
+        fn it_returns_none_when_every_card_pair_is_blocked() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let mut deck = Deck::without(&CardSet::from_str("AsAhAd").unwrap());
+
+            assert_eq!(
+                deck.deal_from_hand_range(&HandRange::from_str("AA").unwrap(), &mut rng),
+                None
+            );
+            assert_eq!(deck.len(), 49);
+        }
+
+        #[test]
+        fn it_returns_none_when_a_weight_is_infinite() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let mut deck = Deck::new();
+            let hand_range = [
+                (CardPair::from_str("AsAh").unwrap(), f32::INFINITY),
+                (CardPair::from_str("KsKh").unwrap(), 0.5),
+            ]
+            .into_iter()
+            .collect::<HandRange>();
+
+            assert_eq!(deck.deal_from_hand_range(&hand_range, &mut rng), None);
+            assert_eq!(deck.len(), 52);
+        }
+    }
+}
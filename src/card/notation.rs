@@ -0,0 +1,347 @@
+use super::{Card, CardSet, ParseCardError, Rank, Suit};
+use alloc::string::ToString;
+use alloc::{vec, vec::Vec};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum Notation {
+    // e.g. "As", "Th"
+    #[default]
+    Ascii,
+    // e.g. "A♠", "T♥"
+    Unicode,
+    // unicode notation colored with ANSI escape codes in four-color deck style
+    Ansi,
+}
+
+pub trait Notate {
+    fn fmt_with(&self, f: &mut Formatter, notation: Notation) -> core::fmt::Result;
+
+    fn notate(&self, notation: Notation) -> Notated<'_, Self> {
+        Notated(self, notation)
+    }
+}
+
+pub struct Notated<'a, T: Notate + ?Sized>(&'a T, Notation);
+
+impl<T: Notate + ?Sized> Display for Notated<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        self.0.fmt_with(f, self.1)
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+fn ansi_color(suit: &Suit) -> &'static str {
+    match suit {
+        Suit::Spade => "\x1b[37m",
+        Suit::Heart => "\x1b[31m",
+        Suit::Diamond => "\x1b[34m",
+        Suit::Club => "\x1b[32m",
+    }
+}
+
+fn unicode_symbol(suit: &Suit) -> char {
+    match suit {
+        Suit::Spade => '♠',
+        Suit::Heart => '♥',
+        Suit::Diamond => '♦',
+        Suit::Club => '♣',
+    }
+}
+
+impl Notate for Rank {
+    fn fmt_with(&self, f: &mut Formatter, _: Notation) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Notate for Suit {
+    fn fmt_with(&self, f: &mut Formatter, notation: Notation) -> core::fmt::Result {
+        match notation {
+            Notation::Ascii => Display::fmt(self, f),
+            Notation::Unicode => write!(f, "{}", unicode_symbol(self)),
+            Notation::Ansi => write!(
+                f,
+                "{}{}{}",
+                ansi_color(self),
+                unicode_symbol(self),
+                ANSI_RESET
+            ),
+        }
+    }
+}
+
+impl Notate for Card {
+    fn fmt_with(&self, f: &mut Formatter, notation: Notation) -> core::fmt::Result {
+        match notation {
+            Notation::Ascii => Display::fmt(self, f),
+            Notation::Unicode => write!(f, "{}{}", self.rank(), unicode_symbol(self.suit())),
+            Notation::Ansi => write!(
+                f,
+                "{}{}{}{}",
+                ansi_color(self.suit()),
+                self.rank(),
+                unicode_symbol(self.suit()),
+                ANSI_RESET
+            ),
+        }
+    }
+}
+
+impl<const N: usize> Notate for [Card; N] {
+    fn fmt_with(&self, f: &mut Formatter, notation: Notation) -> core::fmt::Result {
+        for card in self {
+            card.fmt_with(f, notation)?;
+        }
+
+        Ok(())
+    }
+}
+
+// unknown cards are written as "x" so that the output can be parsed back by `parse_board()`.
+impl<const N: usize> Notate for [Option<Card>; N] {
+    fn fmt_with(&self, f: &mut Formatter, notation: Notation) -> core::fmt::Result {
+        for card in self {
+            match card {
+                Some(card) => card.fmt_with(f, notation)?,
+                None => f.write_str("x")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Notate for CardSet {
+    fn fmt_with(&self, f: &mut Formatter, notation: Notation) -> core::fmt::Result {
+        for card in self {
+            card.fmt_with(f, notation)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum CardToken<'a> {
+    Card(&'a str),
+    Wildcard,
+}
+
+// splits a string of concatenated cards (e.g. "As10hx") into card strings without validating
+// them. "x", "X" and "?" are taken as a wildcard that stands for an unknown card.
+pub(crate) fn tokenize_cards(value: &str) -> Vec<CardToken<'_>> {
+    let mut tokens = vec![];
+    let mut rest = value;
+
+    while let Some(c) = rest.chars().next() {
+        if c == 'x' || c == 'X' || c == '?' {
+            tokens.push(CardToken::Wildcard);
+            rest = &rest[c.len_utf8()..];
+
+            continue;
+        }
+
+        let rank_len = if rest.starts_with("10") {
+            2
+        } else {
+            c.len_utf8()
+        };
+        let suit_len = rest[rank_len..].chars().next().map_or(0, |c| c.len_utf8());
+
+        tokens.push(CardToken::Card(&rest[..rank_len + suit_len]));
+        rest = &rest[rank_len + suit_len..];
+    }
+
+    tokens
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || c == ',' || c == '[' || c == ']'
+}
+
+pub fn parse_cards(value: &str) -> Result<Vec<Option<Card>>, ParseCardError> {
+    let mut cards = vec![];
+
+    for chunk in value.split(is_separator) {
+        for token in tokenize_cards(chunk) {
+            match token {
+                CardToken::Card(card_str) => cards.push(Some(Card::from_str(card_str)?)),
+                CardToken::Wildcard => cards.push(None),
+            }
+        }
+    }
+
+    Ok(cards)
+}
+
+pub fn parse_board(value: &str) -> Result<[Option<Card>; 5], ParseCardError> {
+    let cards = parse_cards(value)?;
+
+    if cards.len() > 5 {
+        return Err(ParseCardError(value.to_string()));
+    }
+
+    let mut board = [None; 5];
+
+    for (i, card) in cards.into_iter().enumerate() {
+        board[i] = card;
+    }
+
+    Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod notate {
+        use super::*;
+
+        #[test]
+        fn it_formats_card_in_ascii() {
+            assert_eq!(
+                Card::new(Rank::Ace, Suit::Spade)
+                    .notate(Notation::Ascii)
+                    .to_string(),
+                "As"
+            );
+        }
+
+        #[test]
+        fn it_formats_card_in_unicode() {
+            assert_eq!(
+                Card::new(Rank::Ace, Suit::Spade)
+                    .notate(Notation::Unicode)
+                    .to_string(),
+                "A♠"
+            );
+            assert_eq!(
+                Card::new(Rank::Ten, Suit::Heart)
+                    .notate(Notation::Unicode)
+                    .to_string(),
+                "T♥"
+            );
+        }
+
+        #[test]
+        fn it_formats_card_in_ansi() {
+            assert_eq!(
+                Card::new(Rank::King, Suit::Heart)
+                    .notate(Notation::Ansi)
+                    .to_string(),
+                "\x1b[31mK♥\x1b[0m"
+            );
+            assert_eq!(
+                Card::new(Rank::Deuce, Suit::Club)
+                    .notate(Notation::Ansi)
+                    .to_string(),
+                "\x1b[32m2♣\x1b[0m"
+            );
+        }
+
+        #[test]
+        fn it_formats_board_in_unicode() {
+            assert_eq!(
+                [
+                    Card::new(Rank::Queen, Suit::Spade),
+                    Card::new(Rank::Eight, Suit::Diamond),
+                    Card::new(Rank::Deuce, Suit::Heart),
+                    Card::new(Rank::Seven, Suit::Club),
+                    Card::new(Rank::Ace, Suit::Spade),
+                ]
+                .notate(Notation::Unicode)
+                .to_string(),
+                "Q♠8♦2♥7♣A♠"
+            );
+        }
+
+        #[test]
+        fn it_formats_incomplete_board_with_wildcards() {
+            assert_eq!(
+                [
+                    Some(Card::new(Rank::Queen, Suit::Spade)),
+                    Some(Card::new(Rank::Eight, Suit::Diamond)),
+                    Some(Card::new(Rank::Deuce, Suit::Heart)),
+                    None,
+                    None,
+                ]
+                .notate(Notation::Unicode)
+                .to_string(),
+                "Q♠8♦2♥xx"
+            );
+        }
+    }
+
+    mod parse_cards {
+        use super::*;
+
+        #[test]
+        fn it_parses_mixed_notations() {
+            assert_eq!(
+                parse_cards("As 10h,K♦[2C]x").unwrap(),
+                vec![
+                    Some(Card::new(Rank::Ace, Suit::Spade)),
+                    Some(Card::new(Rank::Ten, Suit::Heart)),
+                    Some(Card::new(Rank::King, Suit::Diamond)),
+                    Some(Card::new(Rank::Deuce, Suit::Club)),
+                    None,
+                ]
+            );
+        }
+
+        #[test]
+        fn it_fails_parsing_incomplete_card() {
+            assert!(parse_cards("AsK").is_err());
+        }
+    }
+
+    mod parse_board {
+        use super::*;
+
+        #[test]
+        fn it_parses_flop() {
+            assert_eq!(
+                parse_board("Q♠8♦2♥").unwrap(),
+                [
+                    Some(Card::new(Rank::Queen, Suit::Spade)),
+                    Some(Card::new(Rank::Eight, Suit::Diamond)),
+                    Some(Card::new(Rank::Deuce, Suit::Heart)),
+                    None,
+                    None,
+                ]
+            );
+        }
+
+        #[test]
+        fn it_parses_wildcards() {
+            assert_eq!(
+                parse_board("xQs8dx2h").unwrap(),
+                [
+                    None,
+                    Some(Card::new(Rank::Queen, Suit::Spade)),
+                    Some(Card::new(Rank::Eight, Suit::Diamond)),
+                    None,
+                    Some(Card::new(Rank::Deuce, Suit::Heart)),
+                ]
+            );
+        }
+
+        #[test]
+        fn it_round_trips_with_notation() {
+            let board = parse_board("Qs 8d 2h").unwrap();
+
+            assert_eq!(
+                parse_board(&board.notate(Notation::Unicode).to_string()).unwrap(),
+                board
+            );
+        }
+
+        #[test]
+        fn it_fails_parsing_more_than_5_cards() {
+            assert!(parse_board("AsKsQsJsTs9s").is_err());
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
 pub enum Rank {
@@ -57,10 +57,10 @@ impl Rank {
 }
 
 impl Display for Rank {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         let c: char = self.into();
 
-        c.to_string().fmt(f)
+        c.fmt(f)
     }
 }
 
@@ -69,11 +69,11 @@ impl TryFrom<&char> for Rank {
 
     fn try_from(c: &char) -> Result<Self, Self::Error> {
         match c {
-            'A' => Ok(Rank::Ace),
-            'K' => Ok(Rank::King),
-            'Q' => Ok(Rank::Queen),
-            'J' => Ok(Rank::Jack),
-            'T' => Ok(Rank::Ten),
+            'A' | 'a' => Ok(Rank::Ace),
+            'K' | 'k' => Ok(Rank::King),
+            'Q' | 'q' => Ok(Rank::Queen),
+            'J' | 'j' => Ok(Rank::Jack),
+            'T' | 't' => Ok(Rank::Ten),
             '9' => Ok(Rank::Nine),
             '8' => Ok(Rank::Eight),
             '7' => Ok(Rank::Seven),
@@ -99,6 +99,10 @@ impl FromStr for Rank {
     type Err = ();
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "10" {
+            return Ok(Rank::Ten);
+        }
+
         if let Some(c) = value.chars().nth(0) {
             c.try_into()
         } else {
@@ -330,7 +334,7 @@ mod tests {
         fn it_failes_parsing() {
             let result: Result<Rank, ()> = 'X'.try_into();
 
-            assert_eq!(result.is_err(), true);
+            assert!(result.is_err());
         }
     }
 
@@ -402,9 +406,23 @@ mod tests {
             assert_eq!("2".parse::<Rank>().unwrap(), Rank::Deuce);
         }
 
+        #[test]
+        fn it_parses_10_into_ten() {
+            assert_eq!("10".parse::<Rank>().unwrap(), Rank::Ten);
+        }
+
+        #[test]
+        fn it_parses_lowercase_into_broadway() {
+            assert_eq!("a".parse::<Rank>().unwrap(), Rank::Ace);
+            assert_eq!("k".parse::<Rank>().unwrap(), Rank::King);
+            assert_eq!("q".parse::<Rank>().unwrap(), Rank::Queen);
+            assert_eq!("j".parse::<Rank>().unwrap(), Rank::Jack);
+            assert_eq!("t".parse::<Rank>().unwrap(), Rank::Ten);
+        }
+
         #[test]
         fn it_failes_parsing() {
-            assert_eq!("X".parse::<Rank>().is_err(), true);
+            assert!("X".parse::<Rank>().is_err());
         }
     }
 
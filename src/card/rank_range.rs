@@ -1,4 +1,5 @@
 use super::Rank;
+use alloc::vec::Vec;
 
 // NOTE:
 // ideally Rank struct should implement Step trait and achieve the same job but
@@ -38,7 +39,7 @@ impl RankRange {
     }
 }
 
-const RANKS: [Rank; 13] = [
+pub(super) const RANKS: [Rank; 13] = [
     Rank::Ace,
     Rank::King,
     Rank::Queen,
@@ -56,7 +57,7 @@ const RANKS: [Rank; 13] = [
 
 impl IntoIterator for RankRange {
     type Item = Rank;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = alloc::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
         let vec: Vec<Rank> = match self.inclusive {
@@ -1,5 +1,5 @@
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
 pub enum Suit {
@@ -10,10 +10,10 @@ pub enum Suit {
 }
 
 impl Display for Suit {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         let c: char = self.into();
 
-        c.to_string().fmt(f)
+        c.fmt(f)
     }
 }
 
@@ -22,10 +22,10 @@ impl TryFrom<&char> for Suit {
 
     fn try_from(c: &char) -> Result<Self, Self::Error> {
         match c {
-            's' => Ok(Suit::Spade),
-            'h' => Ok(Suit::Heart),
-            'd' => Ok(Suit::Diamond),
-            'c' => Ok(Suit::Club),
+            's' | 'S' | '♠' | '♤' => Ok(Suit::Spade),
+            'h' | 'H' | '♥' | '♡' => Ok(Suit::Heart),
+            'd' | 'D' | '♦' | '♢' => Ok(Suit::Diamond),
+            'c' | 'C' | '♣' | '♧' => Ok(Suit::Club),
             _ => Err(()),
         }
     }
@@ -148,7 +148,7 @@ mod tests {
         fn it_failes_parsing() {
             let result: Result<Suit, ()> = 'X'.try_into();
 
-            assert_eq!(result.is_err(), true);
+            assert!(result.is_err());
         }
     }
 
@@ -175,9 +175,29 @@ mod tests {
             assert_eq!("c".parse::<Suit>().unwrap(), Suit::Club);
         }
 
+        #[test]
+        fn it_parses_uppercase() {
+            assert_eq!("S".parse::<Suit>().unwrap(), Suit::Spade);
+            assert_eq!("H".parse::<Suit>().unwrap(), Suit::Heart);
+            assert_eq!("D".parse::<Suit>().unwrap(), Suit::Diamond);
+            assert_eq!("C".parse::<Suit>().unwrap(), Suit::Club);
+        }
+
+        #[test]
+        fn it_parses_unicode_symbols() {
+            assert_eq!("♠".parse::<Suit>().unwrap(), Suit::Spade);
+            assert_eq!("♥".parse::<Suit>().unwrap(), Suit::Heart);
+            assert_eq!("♦".parse::<Suit>().unwrap(), Suit::Diamond);
+            assert_eq!("♣".parse::<Suit>().unwrap(), Suit::Club);
+            assert_eq!("♤".parse::<Suit>().unwrap(), Suit::Spade);
+            assert_eq!("♡".parse::<Suit>().unwrap(), Suit::Heart);
+            assert_eq!("♢".parse::<Suit>().unwrap(), Suit::Diamond);
+            assert_eq!("♧".parse::<Suit>().unwrap(), Suit::Club);
+        }
+
         #[test]
         fn it_failes_parsing() {
-            assert_eq!("X".parse::<Suit>().is_err(), true);
+            assert!("X".parse::<Suit>().is_err());
         }
     }
 
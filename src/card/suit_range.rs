@@ -1,4 +1,5 @@
 use super::Suit;
+use alloc::vec::Vec;
 
 // NOTE:
 // ideally Rank struct should implement Step trait and achieve the same job but
@@ -38,11 +39,11 @@ impl SuitRange {
     }
 }
 
-const SUITS: [Suit; 4] = [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
+pub(super) const SUITS: [Suit; 4] = [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
 
 impl IntoIterator for SuitRange {
     type Item = Suit;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = alloc::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
         let vec: Vec<Suit> = match self.inclusive {
@@ -1,8 +1,30 @@
+mod backend;
+mod batch;
+mod board;
 mod dp_table;
+#[cfg(feature = "std")]
+mod equity;
+#[cfg(feature = "std")]
 mod flop_exhaustive;
+#[cfg(feature = "std")]
+mod heads_up;
 mod made_hand;
+#[cfg(feature = "std")]
 mod showdown;
 
+#[cfg(feature = "std")]
+pub use backend::RankKeyBackend;
+pub use backend::{Backend, CompactBackend, DefaultBackend, ScalarBackend, TableBackend};
+pub use batch::HandBits;
+pub use board::BoardEvaluator;
+#[cfg(feature = "generator")]
+pub use dp_table::generator;
+#[cfg(feature = "std")]
+pub use equity::EquityAccumulator;
+#[cfg(feature = "std")]
 pub use flop_exhaustive::FlopExhaustiveEvaluator;
-pub use made_hand::MadeHand;
+#[cfg(feature = "std")]
+pub use heads_up::HeadsUpEvaluator;
+pub use made_hand::{MadeHand, MadeHandType};
+#[cfg(feature = "std")]
 pub use showdown::Showdown;
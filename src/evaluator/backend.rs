@@ -0,0 +1,153 @@
+mod compact;
+#[cfg(feature = "std")]
+mod rank_key;
+mod scalar;
+mod table;
+
+use super::{HandBits, MadeHand};
+use crate::card::Card;
+
+pub use compact::CompactBackend;
+#[cfg(feature = "std")]
+pub use rank_key::RankKeyBackend;
+pub use scalar::ScalarBackend;
+pub use table::TableBackend;
+
+// the way to look a hand up. `MadeHand` evaluates hands with `DefaultBackend`, that is chosen by
+// the `compact-backend` and `rank-key-backend` features and `TableBackend` without them.
+pub trait Backend {
+    // the hand by the ranks of the suit that has five or more cards, where the ace is bit 12 and
+    // the deuce is bit 0
+    fn flush(ranks: u16) -> MadeHand;
+
+    // the hand by the ranks of seven cards, that is the made hand when there is no flush
+    fn rainbow(hand: &HandBits) -> MadeHand;
+
+    fn evaluate(cards: &[Card; 7]) -> MadeHand
+    where
+        Self: Sized,
+    {
+        HandBits::from(&cards[..]).made_hand_with::<Self>()
+    }
+}
+
+#[cfg(feature = "rank-key-backend")]
+pub type DefaultBackend = RankKeyBackend;
+
+#[cfg(all(feature = "compact-backend", not(feature = "rank-key-backend")))]
+pub type DefaultBackend = CompactBackend;
+
+#[cfg(not(any(feature = "compact-backend", feature = "rank-key-backend")))]
+pub type DefaultBackend = TableBackend;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Deck;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn assert_backends_agree(hand: &HandBits) {
+        let expected = hand.made_hand_with::<ScalarBackend>();
+
+        assert_eq!(
+            hand.made_hand_with::<TableBackend>(),
+            expected,
+            "{:?}",
+            hand
+        );
+        assert_eq!(
+            hand.made_hand_with::<CompactBackend>(),
+            expected,
+            "{:?}",
+            hand
+        );
+        #[cfg(feature = "std")]
+        assert_eq!(
+            hand.made_hand_with::<RankKeyBackend>(),
+            expected,
+            "{:?}",
+            hand
+        );
+    }
+
+    mod evaluate {
+        use super::*;
+
+        #[test]
+        fn it_evaluates_the_same_across_backends() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+            for _ in 0..100000 {
+                let mut deck = Deck::new();
+
+                deck.shuffle(&mut rng);
+
+                let cards: [Card; 7] = deck.deal_n(7).unwrap().try_into().unwrap();
+
+                assert_eq!(
+                    HandBits::from(&cards[..]).made_hand(),
+                    ScalarBackend::evaluate(&cards),
+                    "{:?}",
+                    cards
+                );
+                assert_backends_agree(&HandBits::from(&cards[..]));
+            }
+        }
+
+        #[test]
+        fn it_evaluates_the_same_across_backends_on_every_rank_combination() {
+            // one hand for each combination of seven ranks, without a flush as long as possible
+            fn visit(hand: HandBits, rank: u8) {
+                if hand.len() == 7 {
+                    assert_backends_agree(&hand);
+
+                    return;
+                }
+
+                if rank == 13 {
+                    return;
+                }
+
+                let mut hand = hand;
+
+                for suit in 0..4 {
+                    visit(hand, rank + 1);
+
+                    if hand.len() == 7 {
+                        return;
+                    }
+
+                    hand = hand | HandBits::from(&Card::from_index(rank * 4 + (rank + suit) % 4));
+                }
+
+                visit(hand, rank + 1);
+            }
+
+            visit(HandBits::empty(), 0);
+        }
+
+        // takes a while, run it with `--release`
+        #[test]
+        #[ignore]
+        fn it_evaluates_the_same_across_backends_on_every_hand() {
+            let cards = Card::all();
+            let mut hands = [HandBits::empty(); 8];
+
+            fn visit(cards: &[Card], hands: &mut [HandBits; 8], len: usize, from: usize) {
+                if len == 7 {
+                    assert_backends_agree(&hands[7]);
+
+                    return;
+                }
+
+                for i in from..(cards.len() - (6 - len)) {
+                    hands[len + 1] = hands[len] | HandBits::from(&cards[i]);
+                    visit(cards, hands, len + 1, i + 1);
+                }
+            }
+
+            visit(&cards, &mut hands, 0, 0);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use super::Backend;
+use crate::evaluator::{HandBits, MadeHand};
+
+// computes the power index from the ranks without lookup tables. the combinations of ranks are
+// ordered by their colexicographic rank, that is the order of the strength of the kickers.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactBackend;
+
+// binomial coefficients up to the number of ranks and the number of cards of a made hand
+const BINOMIALS: [[u16; 6]; 14] = binomials();
+
+// colexicographic ranks of the ten straights
+const STRAIGHTS: [u16; 10] = straights();
+
+const fn binomials() -> [[u16; 6]; 14] {
+    let mut binomials = [[0; 6]; 14];
+    let mut n = 0;
+
+    while n < 14 {
+        binomials[n][0] = 1;
+
+        let mut k = 1;
+
+        while k < 6 && n > 0 {
+            binomials[n][k] = binomials[n - 1][k - 1] + binomials[n - 1][k];
+            k += 1;
+        }
+
+        n += 1;
+    }
+
+    binomials
+}
+
+const fn straights() -> [u16; 10] {
+    let mut straights = [0; 10];
+    let mut i = 0;
+
+    // from the six-high straight to the ace-high straight
+    while i < 9 {
+        straights[i] = colex(0b11111 << i);
+        i += 1;
+    }
+
+    // five-high straight
+    straights[9] = colex(0b1000000001111);
+
+    straights
+}
+
+// ranks are bits from the deuce (bit 0) to the ace (bit 12)
+const fn colex(ranks: u16) -> u16 {
+    let mut ranks = ranks;
+    let mut colex = 0;
+    let mut k = 1;
+
+    while ranks != 0 {
+        colex += BINOMIALS[ranks.trailing_zeros() as usize][k];
+        ranks &= ranks - 1;
+        k += 1;
+    }
+
+    colex
+}
+
+// number of stronger combinations of the same number of ranks out of `rank_len` ranks
+fn index(ranks: u16, rank_len: usize) -> u16 {
+    BINOMIALS[rank_len][ranks.count_ones() as usize] - 1 - colex(ranks)
+}
+
+// number of stronger combinations of five ranks that don't make a straight
+fn unpaired_index(ranks: u16) -> u16 {
+    let colex = colex(ranks);
+
+    index(ranks, 13)
+        - STRAIGHTS
+            .iter()
+            .filter(|straight| **straight > colex)
+            .count() as u16
+}
+
+// the highest `len` ranks
+fn top(ranks: u16, len: u32) -> u16 {
+    let mut ranks = ranks;
+
+    while ranks.count_ones() > len {
+        ranks &= ranks - 1;
+    }
+
+    ranks
+}
+
+// the ranks renumbered without the excluded ones
+fn without(ranks: u16, excluded: u16) -> u16 {
+    let mut ranks = ranks;
+    let mut renumbered = 0;
+
+    while ranks != 0 {
+        let rank = ranks.trailing_zeros();
+
+        renumbered |= 1 << (rank - (excluded & ((1 << rank) - 1)).count_ones());
+        ranks &= ranks - 1;
+    }
+
+    renumbered
+}
+
+// the highest rank of the straight in the ranks. the ace also plays as the lowest rank.
+fn straight_high(ranks: u16) -> Option<u16> {
+    let ranks = (ranks << 1) | (ranks >> 12);
+    let straights = ranks & (ranks >> 1) & (ranks >> 2) & (ranks >> 3) & (ranks >> 4);
+
+    (straights != 0).then(|| 15 - straights.leading_zeros() as u16 + 3)
+}
+
+impl Backend for CompactBackend {
+    fn flush(ranks: u16) -> MadeHand {
+        match straight_high(ranks) {
+            Some(high) => MadeHand(1 + 12 - high),
+            None => MadeHand(323 + unpaired_index(top(ranks, 5))),
+        }
+    }
+
+    fn rainbow(hand: &HandBits) -> MadeHand {
+        // ranks that have one or more cards, two or more cards and so on
+        let mut ranks = [0u16; 5];
+
+        for rank in 0..13 {
+            for at_least in ranks.iter_mut().take(hand.rank_len(rank) as usize + 1) {
+                *at_least |= 1 << (12 - rank);
+            }
+        }
+
+        let [_, singles, pairs, trips, quads] = ranks;
+
+        if quads != 0 {
+            let quad = top(quads, 1);
+            let kicker = top(singles & !quad, 1);
+
+            return MadeHand(11 + index(quad, 13) * 12 + index(without(kicker, quad), 12));
+        }
+
+        if trips != 0 && pairs & !top(trips, 1) != 0 {
+            let trip = top(trips, 1);
+            let pair = top(pairs & !trip, 1);
+
+            return MadeHand(167 + index(trip, 13) * 12 + index(without(pair, trip), 12));
+        }
+
+        if let Some(high) = straight_high(singles) {
+            return MadeHand(1600 + 12 - high);
+        }
+
+        if trips != 0 {
+            let kickers = top(singles & !trips, 2);
+
+            return MadeHand(1610 + index(trips, 13) * 66 + index(without(kickers, trips), 12));
+        }
+
+        if pairs.count_ones() >= 2 {
+            let pairs = top(pairs, 2);
+            let kicker = top(singles & !pairs, 1);
+
+            return MadeHand(2468 + index(pairs, 13) * 11 + index(without(kicker, pairs), 11));
+        }
+
+        if pairs != 0 {
+            let kickers = top(singles & !pairs, 3);
+
+            return MadeHand(3326 + index(pairs, 13) * 220 + index(without(kickers, pairs), 12));
+        }
+
+        MadeHand(6186 + unpaired_index(top(singles, 5)))
+    }
+}
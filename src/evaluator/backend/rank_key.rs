@@ -0,0 +1,65 @@
+use super::{Backend, TableBackend};
+use crate::card::Card;
+use crate::evaluator::{HandBits, MadeHand};
+use alloc::vec;
+use alloc::vec::Vec;
+use std::sync::OnceLock;
+
+// four aces and three kings, see `RANK_KEYS`
+const MAX_RANK_KEY: usize = 4 * 1479181 + 3 * 636345;
+
+// looks the hand up by the sum of the keys of the ranks, in a table of about 16MB that is built on
+// the first use. it's larger but takes a single lookup instead of a hash over the ranks.
+#[derive(Debug, Clone, Copy)]
+pub struct RankKeyBackend;
+
+impl RankKeyBackend {
+    fn table() -> &'static [u16] {
+        static TABLE: OnceLock<Vec<u16>> = OnceLock::new();
+
+        TABLE.get_or_init(|| {
+            let mut table = vec![0; MAX_RANK_KEY + 1];
+
+            fill(&mut table, HandBits::empty(), 0);
+
+            table
+        })
+    }
+}
+
+// fills the table with every combination of seven ranks from the given rank
+fn fill(table: &mut [u16], hand: HandBits, rank: u8) {
+    if hand.len() == 7 {
+        table[hand.rank_key as usize] = TableBackend::rainbow(&hand).power_index();
+
+        return;
+    }
+
+    if rank == 13 {
+        return;
+    }
+
+    let mut hand = hand;
+
+    for suit in 0..4 {
+        fill(table, hand, rank + 1);
+
+        if hand.len() == 7 {
+            return;
+        }
+
+        hand = hand | HandBits::from(&Card::from_index(rank * 4 + suit));
+    }
+
+    fill(table, hand, rank + 1);
+}
+
+impl Backend for RankKeyBackend {
+    fn flush(ranks: u16) -> MadeHand {
+        TableBackend::flush(ranks)
+    }
+
+    fn rainbow(hand: &HandBits) -> MadeHand {
+        MadeHand(RankKeyBackend::table()[hand.rank_key as usize])
+    }
+}
@@ -0,0 +1,94 @@
+use super::Backend;
+use crate::card::Card;
+use crate::evaluator::dp_table::{AS_FLUSH, AS_RAINBOW, DP_REFS};
+use crate::evaluator::{HandBits, MadeHand};
+
+// looks the hand up in the tables of `dp_table` by walking the cards one by one, the way hands
+// were evaluated before `HandBits`. it's slower than the other backends and kept as a reference
+// that doesn't share their bit tricks.
+#[derive(Debug, Clone, Copy)]
+pub struct ScalarBackend;
+
+impl Backend for ScalarBackend {
+    fn flush(ranks: u16) -> MadeHand {
+        MadeHand(AS_FLUSH[ranks as usize])
+    }
+
+    fn rainbow(hand: &HandBits) -> MadeHand {
+        let mut rank_lens = [0; 13];
+
+        for (rank, len) in rank_lens.iter_mut().enumerate() {
+            *len = hand.rank_len(rank as u8);
+        }
+
+        MadeHand(AS_RAINBOW[hash_for_rainbow(&rank_lens) as usize])
+    }
+
+    fn evaluate(cards: &[Card; 7]) -> MadeHand {
+        match find_flush_suit(cards) {
+            Some(suit) => ScalarBackend::flush(ranks_of_suit(cards, suit)),
+            None => {
+                let mut rank_lens = [0; 13];
+
+                for card in cards {
+                    rank_lens[u8::from(card.rank()) as usize] += 1;
+                }
+
+                MadeHand(AS_RAINBOW[hash_for_rainbow(&rank_lens) as usize])
+            }
+        }
+    }
+}
+
+fn find_flush_suit(cards: &[Card; 7]) -> Option<u8> {
+    let mut suit_lens = [0; 4];
+
+    for card in cards {
+        let suit = u8::from(card.suit());
+
+        suit_lens[suit as usize] += 1;
+
+        if suit_lens[suit as usize] >= 5 {
+            return Some(suit);
+        }
+    }
+
+    None
+}
+
+// the ace is bit 12 and the deuce is bit 0
+fn ranks_of_suit(cards: &[Card; 7], suit: u8) -> u16 {
+    let mut ranks = 0;
+
+    for card in cards {
+        if u8::from(card.suit()) == suit {
+            ranks |= 1 << (12 - u8::from(card.rank()));
+        }
+    }
+
+    ranks
+}
+
+// `rank_lens` is the number of cards of each rank from the ace to the deuce. the ranks are
+// hashed from the deuce to the ace.
+fn hash_for_rainbow(rank_lens: &[u8; 13]) -> u16 {
+    let mut remaining_len = rank_lens.iter().sum::<u8>() as usize;
+    let mut hash = 0;
+
+    for rank in (0..13).rev() {
+        let len = rank_lens[rank] as usize;
+
+        if len == 0 {
+            continue;
+        }
+
+        hash += DP_REFS[len][rank][remaining_len];
+        remaining_len -= len;
+
+        if remaining_len == 0 {
+            break;
+        }
+    }
+
+    hash
+}
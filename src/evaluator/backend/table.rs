@@ -0,0 +1,33 @@
+use super::Backend;
+use crate::evaluator::dp_table::{AS_FLUSH, AS_RAINBOW, DP_REFS};
+use crate::evaluator::{HandBits, MadeHand};
+
+// hashes the ranks perfectly and looks the hand up in the tables of `dp_table`, that are about
+// 100KB in total
+#[derive(Debug, Clone, Copy)]
+pub struct TableBackend;
+
+impl Backend for TableBackend {
+    fn flush(ranks: u16) -> MadeHand {
+        MadeHand(AS_FLUSH[ranks as usize])
+    }
+
+    fn rainbow(hand: &HandBits) -> MadeHand {
+        let mut rank_counts = hand.rank_counts;
+        let mut remaining_len = hand.len as usize;
+        let mut hash: u16 = 0;
+
+        // the deuce has the highest field, so that taking the highest non-empty field walks the
+        // ranks from the deuce to the ace as the hash is defined
+        while rank_counts != 0 {
+            let rank = (63 - rank_counts.leading_zeros() as usize) / 3;
+            let len = ((rank_counts >> (rank * 3)) & 0b111) as usize;
+
+            hash += DP_REFS[len][rank][remaining_len];
+            remaining_len -= len;
+            rank_counts &= !(0b111 << (rank * 3));
+        }
+
+        MadeHand(AS_RAINBOW[hash as usize])
+    }
+}
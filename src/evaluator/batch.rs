@@ -0,0 +1,233 @@
+use super::backend::{Backend, DefaultBackend};
+use super::{BoardEvaluator, MadeHand};
+use crate::card::Card;
+use crate::hand_range::CardPair;
+use alloc::vec::Vec;
+use core::ops::BitOr;
+
+const LANE_MASK: u64 = 0x1fff;
+
+// keys of the ranks from the ace to the deuce. the sums of the keys of any seven cards are
+// distinct, so that `RankKeyBackend` indexes the hand directly by the sum.
+const RANK_KEYS: [u32; 13] = [
+    1479181, 636345, 262349, 83661, 22854, 8698, 2031, 453, 98, 22, 5, 1, 0,
+];
+
+// cards in a bit-parallel form. ranks of each suit are packed into 16-bit lanes of `suits` and the
+// number of cards of each rank into 3-bit fields of `rank_counts`, so that combining cards is a
+// bitwise or and an addition without branches, and flushes are found with a popcount per lane.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct HandBits {
+    suits: u64,
+    pub(super) rank_counts: u64,
+    // sum of the keys of the ranks, see `RankKeyBackend`
+    pub(super) rank_key: u32,
+    pub(super) len: u8,
+}
+
+impl HandBits {
+    pub fn empty() -> HandBits {
+        HandBits::default()
+    }
+
+    pub fn len(&self) -> u8 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // the hand made of seven cards. the cards must be distinct.
+    pub fn made_hand(&self) -> MadeHand {
+        self.made_hand_with::<DefaultBackend>()
+    }
+
+    pub fn made_hand_with<B: Backend>(&self) -> MadeHand {
+        debug_assert!(self.len == 7);
+
+        for suit in 0..4 {
+            let lane = self.suit_lane(suit);
+
+            if lane.count_ones() >= 5 {
+                return B::flush(lane as u16);
+            }
+        }
+
+        B::rainbow(self)
+    }
+
+    // ranks of the suit with the ace at bit 12
+    pub(super) fn suit_lane(&self, suit: u8) -> u64 {
+        (self.suits >> (suit as u64 * 16)) & LANE_MASK
+    }
+
+    pub(super) fn rank_len(&self, rank: u8) -> u8 {
+        ((self.rank_counts >> (rank as u64 * 3)) & 0b111) as u8
+    }
+}
+
+impl From<&Card> for HandBits {
+    fn from(card: &Card) -> Self {
+        let rank = u8::from(card.rank()) as u64;
+        let suit = u8::from(card.suit()) as u64;
+
+        HandBits {
+            // the ace is the highest bit of the lane
+            suits: 1 << (suit * 16 + 12 - rank),
+            rank_counts: 1 << (rank * 3),
+            rank_key: RANK_KEYS[rank as usize],
+            len: 1,
+        }
+    }
+}
+
+impl From<&[Card]> for HandBits {
+    fn from(cards: &[Card]) -> Self {
+        cards
+            .iter()
+            .fold(HandBits::empty(), |bits, card| bits | HandBits::from(card))
+    }
+}
+
+impl From<&CardPair> for HandBits {
+    fn from(card_pair: &CardPair) -> Self {
+        HandBits::from(&card_pair[0]) | HandBits::from(&card_pair[1])
+    }
+}
+
+// the cards on both sides must be distinct
+impl BitOr for HandBits {
+    type Output = HandBits;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        HandBits {
+            suits: self.suits | rhs.suits,
+            rank_counts: self.rank_counts + rhs.rank_counts,
+            rank_key: self.rank_key + rhs.rank_key,
+            len: self.len + rhs.len,
+        }
+    }
+}
+
+impl MadeHand {
+    pub fn from_batch(hands: &[[Card; 7]]) -> Vec<MadeHand> {
+        hands
+            .iter()
+            .map(|cards| HandBits::from(&cards[..]).made_hand())
+            .collect()
+    }
+
+    // hands of many hole cards on the same board. the board is evaluated only once.
+    pub fn from_batch_with_board(board: &[Card; 5], hole_cards: &[CardPair]) -> Vec<MadeHand> {
+        let evaluator = BoardEvaluator::new(board);
+
+        hole_cards
+            .iter()
+            .map(|card_pair| evaluator.evaluate(card_pair))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{CardSet, Deck};
+    use crate::evaluator::ScalarBackend;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::str::FromStr;
+
+    fn random_hands(len: usize) -> Vec<[Card; 7]> {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        (0..len)
+            .map(|_| {
+                let mut deck = Deck::new();
+
+                deck.shuffle(&mut rng);
+                deck.deal_n(7).unwrap().try_into().unwrap()
+            })
+            .collect()
+    }
+
+    mod from_batch {
+        use super::*;
+
+        #[test]
+        fn it_evaluates_the_same_as_scalar_path() {
+            let hands = random_hands(10000);
+
+            assert_eq!(
+                MadeHand::from_batch(&hands),
+                hands
+                    .iter()
+                    .map(ScalarBackend::evaluate)
+                    .collect::<Vec<MadeHand>>()
+            );
+        }
+
+        #[test]
+        fn it_evaluates_flushes_and_straight_flushes() {
+            let hands = [
+                "AsKsQsJsTs2h2c",
+                "5s4s3s2sAsKdKc",
+                "Ah9h7h5h3h2hKh",
+                "9d8c7d6d5d4d3c",
+            ]
+            .map(|hand| {
+                let cards = CardSet::from_str(hand)
+                    .unwrap()
+                    .into_iter()
+                    .collect::<Vec<Card>>();
+
+                <[Card; 7]>::try_from(cards).unwrap()
+            });
+
+            assert_eq!(
+                MadeHand::from_batch(&hands),
+                hands.map(|cards| ScalarBackend::evaluate(&cards)).to_vec()
+            );
+        }
+    }
+
+    mod from_batch_with_board {
+        use super::*;
+
+        #[test]
+        fn it_evaluates_the_same_as_scalar_path() {
+            let board = [
+                Card::from_str("Ks").unwrap(),
+                Card::from_str("8s").unwrap(),
+                Card::from_str("2h").unwrap(),
+                Card::from_str("Ts").unwrap(),
+                Card::from_str("8d").unwrap(),
+            ];
+            let board_cards = CardSet::from(board);
+            let cards = (!board_cards).into_iter().collect::<Vec<Card>>();
+            let mut hole_cards = vec![];
+
+            for i in 0..cards.len() {
+                for j in (i + 1)..cards.len() {
+                    hole_cards.push(CardPair::new(cards[i], cards[j]));
+                }
+            }
+
+            assert_eq!(
+                MadeHand::from_batch_with_board(&board, &hole_cards),
+                hole_cards
+                    .iter()
+                    .map(|card_pair| ScalarBackend::evaluate(&[
+                        card_pair[0],
+                        card_pair[1],
+                        board[0],
+                        board[1],
+                        board[2],
+                        board[3],
+                        board[4]
+                    ]))
+                    .collect::<Vec<MadeHand>>()
+            );
+        }
+    }
+}
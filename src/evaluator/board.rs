@@ -0,0 +1,149 @@
+use super::backend::{Backend, DefaultBackend};
+use super::{HandBits, MadeHand};
+use crate::card::Card;
+use crate::hand_range::CardPair;
+
+// evaluates hole cards on a fixed board. everything that depends only on the board is computed in
+// advance, so that evaluating a card pair is a few lookups.
+#[derive(Debug, Clone)]
+pub struct BoardEvaluator {
+    board: [Card; 5],
+    // the suit that has three or more cards on the board and its ranks. no other suit can make a
+    // flush.
+    flush: Option<(u8, u64)>,
+    // hands without a flush for each rank of the hole cards
+    rainbow: [[MadeHand; 13]; 13],
+}
+
+impl BoardEvaluator {
+    // the cards of the board must be distinct
+    pub fn new(board: &[Card; 5]) -> BoardEvaluator {
+        let board_bits = HandBits::from(&board[..]);
+        let flush = (0..4)
+            .map(|suit| (suit, board_bits.suit_lane(suit)))
+            .find(|(_, lane)| lane.count_ones() >= 3);
+        let mut rainbow = [[MadeHand(0); 13]; 13];
+
+        for left in 0..13 {
+            for right in left..13 {
+                // combinations with more than four cards of a rank can't be dealt
+                if (left == right && board_bits.rank_len(left) > 2)
+                    || board_bits.rank_len(left) > 3
+                    || board_bits.rank_len(right) > 3
+                {
+                    continue;
+                }
+
+                // suits are irrelevant here, they only need to be distinct for a pocket pair
+                let hand = DefaultBackend::rainbow(
+                    &(board_bits
+                        | HandBits::from(&Card::from_index(left * 4))
+                        | HandBits::from(&Card::from_index(right * 4 + 1))),
+                );
+
+                rainbow[left as usize][right as usize] = hand;
+                rainbow[right as usize][left as usize] = hand;
+            }
+        }
+
+        BoardEvaluator {
+            board: *board,
+            flush,
+            rainbow,
+        }
+    }
+
+    pub fn board(&self) -> &[Card; 5] {
+        &self.board
+    }
+
+    // the card pair must not contain any card of the board
+    pub fn evaluate(&self, card_pair: &CardPair) -> MadeHand {
+        if let Some((suit, board_lane)) = self.flush {
+            let lane = board_lane | HandBits::from(card_pair).suit_lane(suit);
+
+            if lane.count_ones() >= 5 {
+                return DefaultBackend::flush(lane as u16);
+            }
+        }
+
+        self.rainbow[u8::from(card_pair[0].rank()) as usize][u8::from(card_pair[1].rank()) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{parse_board, CardSet};
+
+    fn parse_full_board(board: &str) -> [Card; 5] {
+        parse_board(board).unwrap().map(Option::unwrap)
+    }
+
+    fn assert_same_as_scalar_path(board: &str) {
+        let board = parse_full_board(board);
+        let evaluator = BoardEvaluator::new(&board);
+        let cards = (!CardSet::from(board)).into_iter().collect::<Vec<Card>>();
+
+        for i in 0..cards.len() {
+            for j in (i + 1)..cards.len() {
+                let card_pair = CardPair::new(cards[i], cards[j]);
+
+                assert_eq!(
+                    evaluator.evaluate(&card_pair),
+                    MadeHand::from([
+                        card_pair[0],
+                        card_pair[1],
+                        board[0],
+                        board[1],
+                        board[2],
+                        board[3],
+                        board[4],
+                    ]),
+                    "{} on {:?}",
+                    card_pair,
+                    board
+                );
+            }
+        }
+    }
+
+    mod evaluate {
+        use super::*;
+
+        #[test]
+        fn it_evaluates_the_same_as_scalar_path() {
+            assert_same_as_scalar_path("Ks8d2hTc5s");
+        }
+
+        #[test]
+        fn it_evaluates_flushes_on_boards_with_three_or_more_suited_cards() {
+            assert_same_as_scalar_path("Ks8s2hTs5d");
+            assert_same_as_scalar_path("Ks8s2sTs5d");
+            assert_same_as_scalar_path("AhKhQhJhTh");
+        }
+
+        #[test]
+        fn it_evaluates_paired_boards() {
+            assert_same_as_scalar_path("KsKdKh8c8s");
+            assert_same_as_scalar_path("2s2d2h2c8s");
+        }
+
+        #[test]
+        fn it_evaluates_straights_through_the_ace() {
+            assert_same_as_scalar_path("As2d3h4cJs");
+            assert_same_as_scalar_path("AsKdQh9c3s");
+        }
+    }
+
+    mod board {
+        use super::*;
+
+        #[test]
+        fn it_returns_board() {
+            let board = parse_full_board("Ks8d2hTc5s");
+
+            assert_eq!(BoardEvaluator::new(&board).board(), &board);
+        }
+    }
+}
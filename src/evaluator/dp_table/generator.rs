@@ -0,0 +1,285 @@
+// generates the lookup tables of `dp_table`. this file is also compiled into the build script, so
+// it must not depend on the rest of the crate. it's public with the `generator` feature, so that
+// variants can generate their own tables.
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+const SUIT_LEN: usize = 4;
+const MADE_HAND_LEN: usize = 5;
+
+// strength of a five-card hand, that is comparable by itself. the greater one is the stronger
+// hand. for hold'em, the category comes first and the ranks that break ties follow.
+pub type Strength = (u8, [u8; MADE_HAND_LEN]);
+
+// the deck and the way hands are compared that the tables are generated for, so that variants
+// like short deck or lowball can reuse the generator with their own rules
+#[derive(Debug, Clone, Copy)]
+pub struct Rules {
+    // the number of ranks in the deck, from the lowest (0) to the highest
+    pub rank_len: usize,
+    // the number of cards the best five are chosen from
+    pub hand_len: usize,
+    // strength of five cards by their ranks and whether they are of the same suit
+    pub strength: fn([u8; MADE_HAND_LEN], bool) -> Strength,
+}
+
+pub const HOLDEM: Rules = Rules {
+    rank_len: 13,
+    hand_len: 7,
+    strength,
+};
+
+// categories of hold'em hands, from the weakest
+pub const HIGH_CARD: u8 = 0;
+pub const PAIR: u8 = 1;
+pub const TWO_PAIR: u8 = 2;
+pub const TRIPS: u8 = 3;
+pub const STRAIGHT: u8 = 4;
+pub const FLUSH: u8 = 5;
+pub const FULL_HOUSE: u8 = 6;
+pub const QUADS: u8 = 7;
+pub const STRAIGHT_FLUSH: u8 = 8;
+
+// strength of hold'em hands, where ranks are from the deuce (0) to the ace (12)
+pub fn strength(ranks: [u8; MADE_HAND_LEN], is_flush: bool) -> Strength {
+    let mut counts = [0u8; 13];
+
+    for rank in ranks {
+        counts[rank as usize] += 1;
+    }
+
+    // ranks grouped by the number of cards, larger groups and higher ranks first
+    let mut groups = (0..13)
+        .rev()
+        .filter(|rank| counts[*rank as usize] > 0)
+        .map(|rank| (counts[rank as usize], rank))
+        .collect::<Vec<(u8, u8)>>();
+
+    groups.sort_by(|a, b| b.cmp(a));
+
+    let mut tie_breakers = [0; MADE_HAND_LEN];
+
+    for (tie_breaker, (_, rank)) in tie_breakers.iter_mut().zip(&groups) {
+        *tie_breaker = *rank;
+    }
+
+    let straight_high = match groups.len() {
+        MADE_HAND_LEN if groups[0].1 - groups[4].1 == 4 => Some(groups[0].1),
+        // the ace plays as the lowest card of five-high straight
+        MADE_HAND_LEN if groups.iter().map(|(_, rank)| *rank).eq([12, 3, 2, 1, 0]) => Some(3),
+        _ => None,
+    };
+
+    let category = match (straight_high, is_flush, groups[0].0, groups[1].0) {
+        (Some(_), true, _, _) => STRAIGHT_FLUSH,
+        (_, _, 4, _) => QUADS,
+        (_, _, 3, 2) => FULL_HOUSE,
+        (_, true, _, _) => FLUSH,
+        (Some(_), _, _, _) => STRAIGHT,
+        (_, _, 3, _) => TRIPS,
+        (_, _, 2, 2) => TWO_PAIR,
+        (_, _, 2, _) => PAIR,
+        _ => HIGH_CARD,
+    };
+
+    match straight_high {
+        Some(high) if category == STRAIGHT || category == STRAIGHT_FLUSH => {
+            (category, [high, 0, 0, 0, 0])
+        }
+        _ => (category, tie_breakers),
+    }
+}
+
+impl Rules {
+    // power indexes of every hand class, from 1 for the strongest
+    pub fn power_indexes(&self) -> HashMap<Strength, u16> {
+        let mut strengths = vec![];
+
+        self.for_each_rank_counts(MADE_HAND_LEN, &mut |counts| {
+            let ranks = ranks_of(counts).try_into().unwrap();
+
+            strengths.push((self.strength)(ranks, false));
+
+            if counts.iter().all(|count| *count <= 1) {
+                strengths.push((self.strength)(ranks, true));
+            }
+        });
+
+        strengths.sort_by(|a, b| b.cmp(a));
+        strengths.dedup();
+
+        strengths
+            .into_iter()
+            .enumerate()
+            .map(|(i, strength)| (strength, i as u16 + 1))
+            .collect()
+    }
+
+    // the strongest five cards out of the given cards
+    pub fn best_strength(&self, ranks: &[u8], is_flush: bool) -> Strength {
+        let mut best = (0, [0; MADE_HAND_LEN]);
+        let mut indexes: [usize; MADE_HAND_LEN] = [0, 1, 2, 3, 4];
+
+        loop {
+            best = best.max((self.strength)(indexes.map(|i| ranks[i]), is_flush));
+
+            // next combination of indexes in lexicographic order
+            let Some(i) = (0..MADE_HAND_LEN)
+                .rev()
+                .find(|i| indexes[*i] < ranks.len() - MADE_HAND_LEN + i)
+            else {
+                return best;
+            };
+
+            indexes[i] += 1;
+
+            for j in (i + 1)..MADE_HAND_LEN {
+                indexes[j] = indexes[j - 1] + 1;
+            }
+        }
+    }
+
+    // calls the function with every combination of `len` cards, as the number of cards of each
+    // rank
+    fn for_each_rank_counts(&self, len: usize, f: &mut impl FnMut(&[u8])) {
+        fn visit(counts: &mut [u8], rank: usize, remaining_len: usize, f: &mut impl FnMut(&[u8])) {
+            if rank == counts.len() {
+                if remaining_len == 0 {
+                    f(counts);
+                }
+
+                return;
+            }
+
+            for count in 0..=remaining_len.min(SUIT_LEN) {
+                counts[rank] = count as u8;
+                visit(counts, rank + 1, remaining_len - count, f);
+            }
+
+            counts[rank] = 0;
+        }
+
+        visit(&mut vec![0; self.rank_len], 0, len, f);
+    }
+
+    // `[len][rank][remaining_len]`, where the rank is from the highest (0) to the lowest as the
+    // crate orders them. it's the number of combinations of the remaining cards that are hashed
+    // before the ones having `len` cards of the rank, given that the ranks are hashed from the
+    // lowest.
+    pub fn dp_refs(&self) -> Vec<Vec<Vec<u16>>> {
+        let mut refs = vec![vec![vec![0; self.hand_len + 1]; self.rank_len]; SUIT_LEN + 1];
+
+        for (len, ranks) in refs.iter_mut().enumerate() {
+            // the ranks after the current one are the higher ones, that is as many as the index
+            for (higher_rank_len, remaining_lens) in ranks.iter_mut().enumerate().skip(1) {
+                for (remaining_len, value) in remaining_lens.iter_mut().enumerate() {
+                    *value = (0..len)
+                        .map(|count| {
+                            combination_len(
+                                higher_rank_len,
+                                remaining_len as isize - count as isize,
+                            )
+                        })
+                        .sum::<u32>() as u16;
+                }
+            }
+        }
+
+        refs
+    }
+
+    // power indexes of flushes by the ranks of the flush suit, where the highest rank is the
+    // highest bit. ranks of less than five cards or more cards than a hand are zero.
+    pub fn as_flush(&self) -> Vec<u16> {
+        let power_indexes = self.power_indexes();
+
+        (0..1u16 << self.rank_len)
+            .map(|bits| {
+                let ranks = (0..self.rank_len as u8)
+                    .filter(|rank| bits & (1 << rank) != 0)
+                    .collect::<Vec<u8>>();
+
+                if (MADE_HAND_LEN..=self.hand_len).contains(&ranks.len()) {
+                    power_indexes[&self.best_strength(&ranks, true)]
+                } else {
+                    0
+                }
+            })
+            .collect()
+    }
+
+    // power indexes of hands without flush by the hash of the number of cards of each rank
+    pub fn as_rainbow(&self) -> Vec<u16> {
+        let power_indexes = self.power_indexes();
+        let refs = self.dp_refs();
+        let mut table = vec![];
+
+        self.for_each_rank_counts(self.hand_len, &mut |counts| {
+            let mut hash = 0;
+            let mut remaining_len = self.hand_len;
+
+            for (rank, count) in counts.iter().enumerate() {
+                let count = *count as usize;
+
+                hash += refs[count][self.rank_len - 1 - rank][remaining_len] as usize;
+                remaining_len -= count;
+            }
+
+            if table.len() <= hash {
+                table.resize(hash + 1, 0);
+            }
+
+            table[hash] = power_indexes[&self.best_strength(&ranks_of(counts), false)];
+        });
+
+        table
+    }
+
+    pub fn write_tables(&self, writer: &mut impl Write) -> io::Result<()> {
+        let refs = self.dp_refs();
+        let as_flush = self.as_flush();
+        let as_rainbow = self.as_rainbow();
+
+        writeln!(
+            writer,
+            "pub static DP_REFS: [[[u16; {}]; {}]; {}] = {:?};",
+            self.hand_len + 1,
+            self.rank_len,
+            SUIT_LEN + 1,
+            refs
+        )?;
+        writeln!(
+            writer,
+            "pub static AS_FLUSH: [u16; {}] = {:?};",
+            as_flush.len(),
+            as_flush
+        )?;
+        writeln!(
+            writer,
+            "pub static AS_RAINBOW: [u16; {}] = {:?};",
+            as_rainbow.len(),
+            as_rainbow
+        )
+    }
+}
+
+fn ranks_of(counts: &[u8]) -> Vec<u8> {
+    (0..counts.len() as u8)
+        .flat_map(|rank| std::iter::repeat_n(rank, counts[rank as usize] as usize))
+        .collect()
+}
+
+// number of ways to have `len` cards in `rank_len` ranks
+fn combination_len(rank_len: usize, len: isize) -> u32 {
+    if len < 0 {
+        return 0;
+    }
+
+    if rank_len == 0 {
+        return (len == 0) as u32;
+    }
+
+    (0..=SUIT_LEN as isize)
+        .map(|count| combination_len(rank_len - 1, len - count))
+        .sum()
+}
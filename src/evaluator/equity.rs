@@ -0,0 +1,323 @@
+use super::{MadeHand, Showdown};
+
+// kahan-babuska (neumaier) summation. the error stays constant instead of growing with the
+// number of additions, which matters when summing millions of tiny probabilities.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    fn add(&mut self, value: f64) {
+        let sum = self.sum + value;
+
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - sum) + value;
+        } else {
+            self.compensation += (value - sum) + self.sum;
+        }
+
+        self.sum = sum;
+    }
+
+    fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+// accumulates equities of players over showdowns. while every showdown has the probability of
+// 1.0, shares of the pot are also counted as integers so that the equity is known exactly.
+#[derive(Debug, PartialEq, Clone)]
+pub struct EquityAccumulator {
+    shares: Vec<CompensatedSum>,
+    total: CompensatedSum,
+    // the pot is split into this many units so that a pot split by any number of winners is an
+    // integer. it's the least common multiple of 1 to the number of players.
+    unit: u64,
+    exact_shares: Option<Vec<u64>>,
+    showdown_len: u64,
+}
+
+impl EquityAccumulator {
+    pub fn new(player_len: usize) -> EquityAccumulator {
+        EquityAccumulator {
+            shares: vec![CompensatedSum::default(); player_len],
+            total: CompensatedSum::default(),
+            unit: (1..=player_len.max(1) as u64).fold(1, lcm),
+            exact_shares: Some(vec![0; player_len]),
+            showdown_len: 0,
+        }
+    }
+
+    pub fn add(&mut self, showdown: &Showdown) {
+        self.add_winners(
+            showdown.players().iter().map(|player| player.is_winner()),
+            showdown.winner_len() as u64,
+            showdown.probability(),
+        );
+    }
+
+    // the same as `add()` but takes the hands of the players directly
+    pub(crate) fn add_hands<I>(&mut self, hands: I, probability: f64)
+    where
+        I: Iterator<Item = MadeHand> + Clone,
+    {
+        let strongest = hands.clone().min();
+        let winner_len = hands
+            .clone()
+            .filter(|hand| Some(*hand) == strongest)
+            .count() as u64;
+
+        self.add_winners(
+            hands.map(|hand| Some(hand) == strongest),
+            winner_len,
+            probability,
+        );
+    }
+
+    // adds showdowns tallied in bulk. `exact_shares` is in units of `unit()` and is `None` when
+    // any of the showdowns has a probability other than 1.0.
+    pub(crate) fn add_tally(
+        &mut self,
+        shares: &[f64],
+        exact_shares: Option<&[u64]>,
+        total: f64,
+        showdown_len: u64,
+    ) {
+        debug_assert!(shares.len() == self.shares.len());
+
+        for (share, tallied) in self.shares.iter_mut().zip(shares) {
+            share.add(*tallied);
+        }
+
+        self.exact_shares = match (self.exact_shares.take(), exact_shares) {
+            (Some(exact), Some(tallied)) => Some(
+                exact
+                    .into_iter()
+                    .zip(tallied)
+                    .map(|(share, tallied)| share + tallied)
+                    .collect(),
+            ),
+            _ => None,
+        };
+        self.total.add(total);
+        self.showdown_len += showdown_len;
+    }
+
+    pub(crate) fn unit(&self) -> u64 {
+        self.unit
+    }
+
+    fn add_winners<I: Iterator<Item = bool>>(
+        &mut self,
+        winners: I,
+        winner_len: u64,
+        probability: f64,
+    ) {
+        if probability != 1.0 {
+            self.exact_shares = None;
+        }
+
+        for (i, is_winner) in winners.enumerate() {
+            debug_assert!(i < self.shares.len());
+
+            if is_winner {
+                self.shares[i].add(probability / winner_len as f64);
+
+                if let Some(exact_shares) = &mut self.exact_shares {
+                    exact_shares[i] += self.unit / winner_len;
+                }
+            }
+        }
+
+        self.total.add(probability);
+        self.showdown_len += 1;
+    }
+
+    // combines the results of another accumulator, e.g. one that ran over another scope
+    pub fn merge(&mut self, other: &EquityAccumulator) {
+        debug_assert!(other.shares.len() == self.shares.len());
+
+        for (share, other_share) in self.shares.iter_mut().zip(&other.shares) {
+            share.add(other_share.sum);
+            share.add(other_share.compensation);
+        }
+
+        self.total.add(other.total.sum);
+        self.total.add(other.total.compensation);
+        self.showdown_len += other.showdown_len;
+        self.exact_shares = match (self.exact_shares.take(), &other.exact_shares) {
+            (Some(shares), Some(other_shares)) => Some(
+                shares
+                    .into_iter()
+                    .zip(other_shares)
+                    .map(|(share, other_share)| share + other_share)
+                    .collect(),
+            ),
+            _ => None,
+        };
+    }
+
+    pub fn showdown_len(&self) -> u64 {
+        self.showdown_len
+    }
+
+    pub fn total_probability(&self) -> f64 {
+        self.total.value()
+    }
+
+    pub fn equity(&self, player_index: usize) -> f64 {
+        if let Some((numerator, denominator)) = self.exact_equity(player_index) {
+            return numerator as f64 / denominator as f64;
+        }
+
+        self.shares[player_index].value() / self.total.value()
+    }
+
+    pub fn equities(&self) -> Vec<f64> {
+        (0..self.shares.len()).map(|i| self.equity(i)).collect()
+    }
+
+    // the equity as an irreducible fraction. it's available only when every showdown has had
+    // the probability of 1.0.
+    pub fn exact_equity(&self, player_index: usize) -> Option<(u64, u64)> {
+        let exact_shares = self.exact_shares.as_ref()?;
+
+        if self.showdown_len == 0 {
+            return None;
+        }
+
+        let numerator = exact_shares[player_index];
+        let denominator = self.unit * self.showdown_len;
+        let divisor = gcd(numerator, denominator);
+
+        Some((numerator / divisor, denominator / divisor))
+    }
+}
+
+impl Extend<Showdown> for EquityAccumulator {
+    fn extend<T: IntoIterator<Item = Showdown>>(&mut self, iter: T) {
+        for showdown in iter {
+            self.add(&showdown);
+        }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_board;
+    use crate::evaluator::FlopExhaustiveEvaluator;
+    use crate::hand_range::HandRange;
+    use std::str::FromStr;
+
+    fn accumulate(board: &str, players: &[&str]) -> EquityAccumulator {
+        let players = players
+            .iter()
+            .map(|player| HandRange::from_str(player).unwrap())
+            .collect::<Vec<HandRange>>();
+        let mut accumulator = EquityAccumulator::new(players.len());
+
+        accumulator
+            .extend(FlopExhaustiveEvaluator::new(&parse_board(board).unwrap(), &players).unwrap());
+
+        accumulator
+    }
+
+    mod exact_equity {
+        use super::*;
+
+        #[test]
+        fn it_returns_exact_equity_of_set_over_set() {
+            let accumulator = accumulate("Jd5d2c", &["JsJh", "5s5h"]);
+
+            assert_eq!(accumulator.showdown_len(), 990);
+            assert_eq!(accumulator.exact_equity(0), Some((947, 990)));
+            assert_eq!(accumulator.exact_equity(1), Some((43, 990)));
+            assert_eq!(accumulator.equity(0), 947.0 / 990.0);
+        }
+
+        #[test]
+        fn it_counts_split_pots_exactly() {
+            let accumulator = accumulate("AsKsQh", &["JdTd", "JcTc", "2h2c"]);
+
+            assert_eq!(accumulator.exact_equity(0), accumulator.exact_equity(1));
+            assert_eq!(
+                accumulator
+                    .equities()
+                    .iter()
+                    .map(|equity| (equity * 1e12).round())
+                    .sum::<f64>(),
+                1e12
+            );
+        }
+
+        #[test]
+        fn it_returns_none_when_probability_is_weighted() {
+            let accumulator = accumulate("Jd5d2c", &["JsJh:0.5", "5s5h"]);
+
+            assert_eq!(accumulator.exact_equity(0), None);
+            assert!((accumulator.equity(0) - 947.0 / 990.0).abs() < 1e-12);
+            assert!((accumulator.total_probability() - 495.0).abs() < 1e-12);
+        }
+
+        #[test]
+        fn it_returns_none_before_any_showdown() {
+            assert_eq!(EquityAccumulator::new(2).exact_equity(0), None);
+        }
+    }
+
+    mod merge {
+        use super::*;
+
+        #[test]
+        fn it_merges_accumulators_over_different_scopes() {
+            let board = parse_board("Jd5d2c").unwrap();
+            let players = vec![
+                HandRange::from_str("JsJh").unwrap(),
+                HandRange::from_str("5s5h").unwrap(),
+            ];
+            let mut left_evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
+            let mut right_evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
+            let mut left = EquityAccumulator::new(2);
+            let mut right = EquityAccumulator::new(2);
+
+            left_evaluator.scope(0, 1, 20, 21);
+            right_evaluator.scope(20, 21, 48, 49);
+            left.extend(left_evaluator);
+            right.extend(right_evaluator);
+            left.merge(&right);
+
+            assert_eq!(left, accumulate("Jd5d2c", &["JsJh", "5s5h"]));
+        }
+    }
+
+    mod equity {
+        use super::*;
+
+        #[test]
+        fn it_matches_exact_equity_for_weighted_ranges() {
+            let exact = accumulate("Qs8d2h", &["JJ+", "A2s+"]);
+            let weighted = accumulate("Qs8d2h", &["JJ+:0.1", "A2s+:0.3"]);
+
+            for i in 0..2 {
+                let (numerator, denominator) = exact.exact_equity(i).unwrap();
+
+                assert!((weighted.equity(i) - numerator as f64 / denominator as f64).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -1,8 +1,7 @@
 use super::showdown::Showdown;
-use crate::card::{Card, RankRange, SuitRange};
-use crate::hand_range::{CardPair, HandRange};
-use fxhash::FxBuildHasher;
-use std::collections::HashSet;
+use super::{BoardEvaluator, EquityAccumulator, MadeHand};
+use crate::card::{Card, CardSet};
+use crate::hand_range::{CardPair, HandRange, InvalidHandRangeError};
 
 pub struct FlopExhaustiveEvaluator {
     board: [Option<Card>; 5],
@@ -14,15 +13,24 @@ pub struct FlopExhaustiveEvaluator {
 }
 
 impl FlopExhaustiveEvaluator {
-    pub fn new(board: &[Option<Card>; 5], players: &Vec<HandRange>) -> Self {
-        Self {
-            board: board.clone(),
-            players: players.clone(),
+    // ranges with probabilities out of 0 to 1 are rejected since they would make the probability
+    // of each showdown meaningless.
+    pub fn new(
+        board: &[Option<Card>; 5],
+        players: &[HandRange],
+    ) -> Result<Self, InvalidHandRangeError> {
+        for player in players {
+            player.validate()?;
+        }
+
+        Ok(Self {
+            board: *board,
+            players: players.to_vec(),
             turn_from: 0,
             river_from: 1,
             turn_to: 48,
             river_to: 49,
-        }
+        })
     }
 
     pub fn scope(&mut self, turn_from: u8, river_from: u8, turn_to: u8, river_to: u8) {
@@ -37,7 +45,25 @@ impl FlopExhaustiveEvaluator {
     }
 }
 
-impl<'p> IntoIterator for FlopExhaustiveEvaluator {
+impl FlopExhaustiveEvaluator {
+    // the same as accumulating every showdown but much faster since showdowns are not
+    // materialized
+    pub fn equities(self) -> EquityAccumulator {
+        let mut iterator = self.into_iter();
+        let mut accumulator = EquityAccumulator::new(iterator.player_entries.len());
+
+        while let Some(probability) = iterator.deal_next() {
+            accumulator.add_hands(
+                iterator.hole_cards.iter().map(|(_, hand)| *hand),
+                probability,
+            );
+        }
+
+        accumulator
+    }
+}
+
+impl IntoIterator for FlopExhaustiveEvaluator {
     type Item = Showdown;
     type IntoIter = FlopExhaustiveEvaluatorIterator;
 
@@ -49,149 +75,179 @@ impl<'p> IntoIterator for FlopExhaustiveEvaluator {
 pub struct FlopExhaustiveEvaluatorIterator {
     turn_to: u8,
     river_to: u8,
-    player_entries: Vec<Vec<(CardPair, f32)>>,
+    // card pairs of each player along with their cards as a bitmask for blocker checks
+    player_entries: Vec<Vec<(CardPair, CardSet, f32)>>,
     current_deck: [Card; 49],
-    current_board: [Option<Card>; 5],
-    current_used_cards: HashSet<Card, FxBuildHasher>,
+    current_board: [Card; 5],
     current_turn_index: u8,
     current_river_index: u8,
-    current_player_indexes: Vec<u8>,
+    current_player_indexes: Vec<usize>,
+    // hand of each card pair on the current runout. `None` when the card pair is blocked by the
+    // board. every card pair is evaluated once per runout instead of once per showdown.
+    hands: Vec<Vec<Option<MadeHand>>>,
+    hands_runout: Option<(u8, u8)>,
+    // reused across `next()` calls to avoid allocating on every iteration
+    hole_cards: Vec<(CardPair, MadeHand)>,
 }
 
 impl FlopExhaustiveEvaluatorIterator {
     fn new(evaluator: &FlopExhaustiveEvaluator) -> Self {
-        let mut player_entries = vec![vec![]; evaluator.players.len()];
-
-        for (player_index, player) in evaluator.players.iter().enumerate() {
-            for (card_pair, probability) in player.card_pairs() {
-                player_entries[player_index].push((*card_pair, *probability));
-            }
-        }
-
-        let mut current_deck = Vec::with_capacity(52);
-
-        for rank in RankRange::all() {
-            for suit in SuitRange::all() {
-                let card = Card::new(rank, suit);
-
-                if evaluator
-                    .board
+        let player_entries = evaluator
+            .players
+            .iter()
+            .map(|player| {
+                player
                     .iter()
-                    .filter(|c| c.is_some())
-                    .all(|c| (*c).unwrap() != card)
-                {
-                    current_deck.push(card);
-                }
-            }
-        }
+                    .map(|(card_pair, probability)| {
+                        (*card_pair, CardSet::from(card_pair), *probability)
+                    })
+                    .collect::<Vec<(CardPair, CardSet, f32)>>()
+            })
+            .collect::<Vec<Vec<(CardPair, CardSet, f32)>>>();
+
+        let flop = evaluator
+            .board
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<Card>>();
+        let flop_cards: CardSet = flop.iter().collect();
+        let current_deck: Vec<Card> = (!flop_cards).into_iter().collect();
+
+        // nothing can be dealt to a player with an empty range
+        let is_exhausted = player_entries.iter().any(|entries| entries.is_empty());
+
+        let hands = player_entries
+            .iter()
+            .map(|entries| vec![None; entries.len()])
+            .collect();
 
         Self {
             turn_to: evaluator.turn_to,
             river_to: evaluator.river_to,
             player_entries,
             current_deck: current_deck.try_into().unwrap(),
-            current_board: evaluator.board.clone(),
-            current_used_cards: HashSet::with_capacity_and_hasher(
-                2 + evaluator.players.len() * 2,
-                FxBuildHasher::default(),
-            ),
-            current_turn_index: evaluator.turn_from,
-            current_river_index: evaluator.river_from,
+            // the turn and the river are filled in `deal()`
+            current_board: [flop[0], flop[1], flop[2], flop[0], flop[0]],
+            current_turn_index: if is_exhausted {
+                evaluator.turn_to
+            } else {
+                evaluator.turn_from
+            },
+            current_river_index: if is_exhausted {
+                evaluator.river_to
+            } else {
+                evaluator.river_from
+            },
             current_player_indexes: vec![0; evaluator.players.len()],
+            hands,
+            hands_runout: None,
+            hole_cards: Vec::with_capacity(evaluator.players.len()),
         }
     }
-}
 
-impl Iterator for FlopExhaustiveEvaluatorIterator {
-    type Item = Showdown;
+    fn evaluate_runout(&mut self) {
+        let board = self.current_board;
+        let board_cards = CardSet::from(board);
+        let evaluator = BoardEvaluator::new(&board);
 
-    fn next(&mut self) -> Option<Showdown> {
-        if self.current_turn_index >= self.turn_to && self.current_river_index >= self.river_to {
-            return None;
+        for (player_entry, hands) in self.player_entries.iter().zip(&mut self.hands) {
+            for ((card_pair, cards, _), hand) in player_entry.iter().zip(hands.iter_mut()) {
+                *hand = board_cards
+                    .is_disjoint(cards)
+                    .then(|| evaluator.evaluate(card_pair));
+            }
         }
+    }
 
-        let turn = self.current_deck[self.current_turn_index as usize];
-        let river = self.current_deck[self.current_river_index as usize];
-
-        self.current_board[3] = Some(turn);
-        self.current_board[4] = Some(river);
+    // deals the current card pairs of each player. returns the index of the first player whose
+    // card pair collides with the board or the other players.
+    fn deal(&mut self) -> Result<f64, usize> {
+        let runout = (self.current_turn_index, self.current_river_index);
 
-        self.current_used_cards.insert(turn);
-        self.current_used_cards.insert(river);
+        if self.hands_runout != Some(runout) {
+            self.current_board[3] = self.current_deck[self.current_turn_index as usize];
+            self.current_board[4] = self.current_deck[self.current_river_index as usize];
+            self.evaluate_runout();
+            self.hands_runout = Some(runout);
+        }
 
-        let mut player_card_pairs = vec![];
-        let mut probability: f32 = 1.0;
+        let mut used_cards = CardSet::empty();
+        let mut probability: f64 = 1.0;
 
-        let mut is_materialized = true;
+        self.hole_cards.clear();
 
         for (player_index, player_entry) in self.player_entries.iter().enumerate() {
-            let entry = player_entry[self.current_player_indexes[player_index] as usize];
+            let entry_index = self.current_player_indexes[player_index];
+            let (card_pair, cards, player_probability) = player_entry[entry_index];
 
-            if self.current_used_cards.contains(&entry.0[0])
-                || self.current_used_cards.contains(&entry.0[1])
-            {
-                is_materialized = false;
-            }
-
-            player_card_pairs.push(entry.0);
-            probability *= entry.1;
-        }
+            let hand = match self.hands[player_index][entry_index] {
+                Some(hand) if used_cards.is_disjoint(&cards) => hand,
+                _ => return Err(player_index),
+            };
 
-        let mut showdown = None;
-
-        if is_materialized {
-            showdown = Showdown::new(
-                player_card_pairs,
-                [
-                    self.current_board[0].unwrap(),
-                    self.current_board[1].unwrap(),
-                    self.current_board[2].unwrap(),
-                    self.current_board[3].unwrap(),
-                    self.current_board[4].unwrap(),
-                ],
-                probability,
-            );
+            used_cards = used_cards | cards;
+            probability *= player_probability as f64;
+            self.hole_cards.push((card_pair, hand));
         }
 
-        let mut player_index_to_increment = None;
-
-        for i in 0..self.current_player_indexes.len() {
-            let ri = self.current_player_indexes.len() - i - 1;
+        Ok(probability)
+    }
 
-            if self.current_player_indexes[ri] < self.player_entries[ri].len() as u8 - 1 {
-                player_index_to_increment = Some(ri);
+    // deals the next combination that no card collides in and leaves it in `hole_cards` and
+    // `current_board`. returns the probability of the combination.
+    fn deal_next(&mut self) -> Option<f64> {
+        while self.current_turn_index < self.turn_to || self.current_river_index < self.river_to {
+            match self.deal() {
+                Ok(probability) => {
+                    self.advance(self.current_player_indexes.len());
 
-                break;
+                    return Some(probability);
+                }
+                Err(blocked_player_index) => self.advance(blocked_player_index + 1),
             }
         }
 
-        self.current_board[3] = None;
-        self.current_board[4] = None;
-
-        self.current_used_cards.clear();
+        None
+    }
 
-        if let Some(player_index_to_increment) = player_index_to_increment {
-            self.current_player_indexes[player_index_to_increment] += 1;
+    // moves to the next combination of card pairs of the first `player_len` players, resetting
+    // the players after them. when a player is blocked, every combination of the players after
+    // it is skipped at once.
+    fn advance(&mut self, player_len: usize) {
+        for i in (0..player_len).rev() {
+            if self.current_player_indexes[i] + 1 < self.player_entries[i].len() {
+                self.current_player_indexes[i] += 1;
+                self.current_player_indexes[i + 1..].fill(0);
 
-            for i in (player_index_to_increment + 1)..self.current_player_indexes.len() {
-                self.current_player_indexes[i] = 0;
+                return;
             }
-
-            return showdown.or_else(|| self.next());
         }
 
+        self.current_player_indexes.fill(0);
+
         if self.current_river_index < 48 {
             self.current_river_index += 1;
-            self.current_player_indexes.fill(0);
 
-            return showdown.or_else(|| self.next());
+            return;
         }
 
         self.current_turn_index += 1;
         self.current_river_index = self.current_turn_index + 1;
-        self.current_player_indexes.fill(0);
+    }
+}
+
+impl Iterator for FlopExhaustiveEvaluatorIterator {
+    type Item = Showdown;
 
-        showdown.or_else(|| self.next())
+    fn next(&mut self) -> Option<Showdown> {
+        let probability = self.deal_next()?;
+
+        Some(Showdown::from_hands(
+            &self.hole_cards,
+            self.current_board,
+            probability,
+        ))
     }
 }
 
@@ -199,9 +255,28 @@ impl Iterator for FlopExhaustiveEvaluatorIterator {
 mod tests {
     use super::*;
 
+    mod new {
+        use super::*;
+        use std::str::FromStr;
+
+        #[test]
+        fn it_rejects_range_with_invalid_probability() {
+            let card_pair = CardPair::from_str("AsAh").unwrap();
+            let players = vec![
+                HandRange::from_str("KK").unwrap(),
+                HandRange::from_iter([(card_pair, 1.5)]),
+            ];
+
+            assert_eq!(
+                FlopExhaustiveEvaluator::new(&[None; 5], &players).err(),
+                Some(InvalidHandRangeError::ProbabilityOutOfRange(card_pair, 1.5))
+            );
+        }
+    }
+
     mod iterator {
         use super::*;
-        use crate::card::{Rank, Suit};
+        use crate::card::{parse_board, Rank, Suit};
         use insta::*;
         use std::str::FromStr;
 
@@ -219,7 +294,7 @@ mod tests {
                 HandRange::from_str("4d3c:1").unwrap(),
             ];
 
-            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players);
+            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
             evaluator.scope(0, 1, 2, 25);
 
             let result: Vec<Showdown> = evaluator.into_iter().collect();
@@ -242,7 +317,7 @@ mod tests {
                 HandRange::from_str("Td8c:1").unwrap(),
             ];
 
-            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players);
+            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
             evaluator.scope(10, 43, 14, 18);
 
             let result: Vec<Showdown> = evaluator.into_iter().collect();
@@ -265,7 +340,7 @@ mod tests {
                 HandRange::from_str("JdJc:1").unwrap(),
             ];
 
-            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players);
+            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
             evaluator.scope(32, 48, 47, 49);
 
             let result: Vec<Showdown> = evaluator.into_iter().collect();
@@ -289,5 +364,165 @@ mod tests {
             );
             assert_debug_snapshot!(result);
         }
+
+        #[test]
+        fn it_iterates_in_the_same_order_regardless_of_insertion_order() {
+            let board = [
+                Some(Card::new(Rank::Queen, Suit::Spade)),
+                Some(Card::new(Rank::Eight, Suit::Diamond)),
+                Some(Card::new(Rank::Deuce, Suit::Heart)),
+                None,
+                None,
+            ];
+            let forward = vec![
+                HandRange::from_str("JJ+,AKs").unwrap(),
+                HandRange::from_str("A2s+").unwrap(),
+            ];
+            let backward = vec![
+                forward[0]
+                    .iter()
+                    .rev()
+                    .map(|(card_pair, probability)| (*card_pair, *probability))
+                    .collect::<HandRange>(),
+                forward[1]
+                    .iter()
+                    .rev()
+                    .map(|(card_pair, probability)| (*card_pair, *probability))
+                    .collect::<HandRange>(),
+            ];
+
+            let mut left = FlopExhaustiveEvaluator::new(&board, &forward).unwrap();
+            let mut right = FlopExhaustiveEvaluator::new(&board, &backward).unwrap();
+            left.scope(0, 1, 1, 5);
+            right.scope(0, 1, 1, 5);
+
+            let left = left
+                .into_iter()
+                .map(|showdown| format!("{:?}", showdown))
+                .collect::<Vec<String>>();
+            let right = right
+                .into_iter()
+                .map(|showdown| format!("{:?}", showdown))
+                .collect::<Vec<String>>();
+
+            assert!(!left.is_empty());
+            assert_eq!(left, right);
+        }
+
+        #[test]
+        fn it_skips_card_pairs_colliding_with_other_players() {
+            let board = parse_board("Qs8d2h").unwrap();
+            let players = vec![
+                HandRange::from_str("AA").unwrap(),
+                HandRange::from_str("AA").unwrap(),
+            ];
+            let evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
+            let mut len = 0;
+
+            for showdown in evaluator {
+                let cards = showdown
+                    .players()
+                    .iter()
+                    .map(|player| CardSet::from(player.hole_cards()))
+                    .fold(CardSet::from(*showdown.board()), |cards, hole_cards| {
+                        assert!(cards.is_disjoint(&hole_cards));
+
+                        cards | hole_cards
+                    });
+
+                assert_eq!(cards.len(), 9);
+
+                len += 1;
+            }
+
+            // every ace is taken by the players, so the runouts are dealt from 45 cards
+            assert_eq!(len, 6 * 990);
+        }
+
+        #[test]
+        fn it_iterates_range_with_more_than_255_card_pairs() {
+            let board = parse_board("Qs8d2h").unwrap();
+            let hand_range = HandRange::from_str("22+,A2s+,A2o+,K2s+,K2o+").unwrap();
+            let mut evaluator =
+                FlopExhaustiveEvaluator::new(&board, std::slice::from_ref(&hand_range)).unwrap();
+            let flop_cards: CardSet = board.iter().flatten().collect();
+            let deck = (!flop_cards).into_iter().collect::<Vec<Card>>();
+
+            evaluator.scope(0, 1, 1, 2);
+
+            assert_eq!(hand_range.len(), 446);
+            assert_eq!(
+                evaluator.into_iter().count(),
+                deck[1..]
+                    .iter()
+                    .map(|river| hand_range
+                        .excluding(&(flop_cards | CardSet::from([deck[0], *river])))
+                        .len())
+                    .sum::<usize>()
+            );
+        }
+
+        #[test]
+        fn it_yields_the_same_showdowns_as_evaluating_each_of_them() {
+            let board = parse_board("Ks8d2h").unwrap();
+            let players = vec![
+                HandRange::from_str("88+,AKs").unwrap(),
+                HandRange::from_str("KQs,K8s,22").unwrap(),
+                HandRange::from_str("T9s,98s").unwrap(),
+            ];
+            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
+
+            evaluator.scope(3, 4, 4, 10);
+
+            for showdown in evaluator {
+                let expected = Showdown::new(
+                    showdown
+                        .players()
+                        .iter()
+                        .map(|player| player.hole_cards())
+                        .collect(),
+                    *showdown.board(),
+                    showdown.probability(),
+                )
+                .unwrap();
+
+                assert_eq!(format!("{:?}", showdown), format!("{:?}", expected));
+            }
+        }
+
+        #[test]
+        fn it_accumulates_the_same_equities_as_iterating() {
+            let board = parse_board("Ks8d2h").unwrap();
+            let players = vec![
+                HandRange::from_str("88+:0.5,AKs").unwrap(),
+                HandRange::from_str("KQs,K8s:0.25,22").unwrap(),
+                HandRange::from_str("T9s,98s").unwrap(),
+            ];
+            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
+            let mut accumulator = EquityAccumulator::new(players.len());
+
+            evaluator.scope(0, 1, 8, 9);
+            accumulator.extend(evaluator);
+
+            let mut evaluator = FlopExhaustiveEvaluator::new(&board, &players).unwrap();
+
+            evaluator.scope(0, 1, 8, 9);
+
+            assert_eq!(evaluator.equities(), accumulator);
+        }
+
+        #[test]
+        fn it_iterates_nothing_when_range_is_empty() {
+            let board = parse_board("Qs8d2h").unwrap();
+            let players = vec![HandRange::from_str("AA").unwrap(), HandRange::empty()];
+
+            assert_eq!(
+                FlopExhaustiveEvaluator::new(&board, &players)
+                    .unwrap()
+                    .into_iter()
+                    .count(),
+                0
+            );
+        }
     }
 }
@@ -0,0 +1,405 @@
+use super::{BoardEvaluator, EquityAccumulator};
+use crate::card::{Card, CardSet};
+use crate::hand_range::{CardPair, HandRange, InvalidHandRangeError};
+use std::ops::{Add, Sub};
+
+// range vs range equity of two players on every runout from the flop. instead of pairing up
+// every combination of card pairs, card pairs of both players are sorted by strength and swept
+// once per runout while removing the weight of card pairs that share a card.
+pub struct HeadsUpEvaluator {
+    board: [Option<Card>; 5],
+    players: [HandRange; 2],
+    turn_from: u8,
+    river_from: u8,
+    turn_to: u8,
+    river_to: u8,
+}
+
+impl HeadsUpEvaluator {
+    pub fn new(
+        board: &[Option<Card>; 5],
+        players: &[HandRange; 2],
+    ) -> Result<Self, InvalidHandRangeError> {
+        for player in players {
+            player.validate()?;
+        }
+
+        Ok(Self {
+            board: *board,
+            players: players.clone(),
+            turn_from: 0,
+            river_from: 1,
+            turn_to: 48,
+            river_to: 49,
+        })
+    }
+
+    // the same as `FlopExhaustiveEvaluator::scope()`
+    pub fn scope(&mut self, turn_from: u8, river_from: u8, turn_to: u8, river_to: u8) {
+        debug_assert!(turn_from <= turn_to);
+        debug_assert!(turn_from < river_from);
+        debug_assert!(turn_to < river_to);
+
+        self.turn_from = turn_from;
+        self.river_from = river_from;
+        self.turn_to = turn_to;
+        self.river_to = river_to;
+    }
+
+    // accumulates the same equities as `FlopExhaustiveEvaluator::equities()` does over the same
+    // scope. the sums may differ in the last bits since they are added up in another order.
+    pub fn equities(&self) -> EquityAccumulator {
+        let flop = self.board.iter().flatten().copied().collect::<Vec<Card>>();
+        let flop_cards: CardSet = flop.iter().collect();
+        let deck = (!flop_cards).into_iter().collect::<Vec<Card>>();
+        let mut accumulator = EquityAccumulator::new(2);
+        let mut sweep = Sweep::new();
+        let players = self.players.clone().map(|player| {
+            player
+                .iter()
+                .map(|(card_pair, probability)| (*card_pair, *probability as f64))
+                .collect::<Vec<(CardPair, f64)>>()
+        });
+        let mut turn_index = self.turn_from;
+        let mut river_index = self.river_from;
+
+        debug_assert!(deck.len() == 49);
+
+        while turn_index < self.turn_to || river_index < self.river_to {
+            let board = [
+                flop[0],
+                flop[1],
+                flop[2],
+                deck[turn_index as usize],
+                deck[river_index as usize],
+            ];
+
+            sweep.run(&board, &players, &mut accumulator);
+
+            if river_index < 48 {
+                river_index += 1;
+            } else {
+                turn_index += 1;
+                river_index = turn_index + 1;
+            }
+        }
+
+        accumulator
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Tally {
+    weight: f64,
+    count: u64,
+}
+
+impl Add for Tally {
+    type Output = Tally;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Tally {
+            weight: self.weight + rhs.weight,
+            count: self.count + rhs.count,
+        }
+    }
+}
+
+impl Sub for Tally {
+    type Output = Tally;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Tally {
+            weight: self.weight - rhs.weight,
+            count: self.count - rhs.count,
+        }
+    }
+}
+
+// the number of card pairs that can be dealt from a full deck. 52 choose 2.
+const CARD_PAIR_LEN: usize = 1326;
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    // card indexes in ascending order
+    cards: [usize; 2],
+    weight: f64,
+    power_index: u16,
+}
+
+impl Entry {
+    // the index of the card pair among the 1326 ones
+    fn pair_index(&self) -> usize {
+        self.cards[1] * (self.cards[1] - 1) / 2 + self.cards[0]
+    }
+
+    fn tally(&self) -> Tally {
+        Tally {
+            weight: self.weight,
+            count: 1,
+        }
+    }
+}
+
+// tallies of inserted card pairs, kept per card and per card pair so that the tally of the card
+// pairs not sharing a card with a given one is obtained in O(1) by inclusion-exclusion. only the
+// slots of inserted card pairs are reset so that narrow ranges don't pay for the whole table.
+struct CardRemovalTally {
+    total: Tally,
+    cards: [Tally; 52],
+    pairs: Vec<Tally>,
+}
+
+impl CardRemovalTally {
+    fn new() -> Self {
+        CardRemovalTally {
+            total: Tally::default(),
+            cards: [Tally::default(); 52],
+            pairs: vec![Tally::default(); CARD_PAIR_LEN],
+        }
+    }
+
+    // `entries` must include every inserted entry
+    fn clear(&mut self, entries: &[Entry]) {
+        self.total = Tally::default();
+        self.cards.fill(Tally::default());
+
+        for entry in entries {
+            self.pairs[entry.pair_index()] = Tally::default();
+        }
+    }
+
+    fn insert(&mut self, entry: &Entry) {
+        self.total = self.total + entry.tally();
+        self.cards[entry.cards[0]] = self.cards[entry.cards[0]] + entry.tally();
+        self.cards[entry.cards[1]] = self.cards[entry.cards[1]] + entry.tally();
+        self.pairs[entry.pair_index()] = self.pairs[entry.pair_index()] + entry.tally();
+    }
+
+    fn disjoint(&self, entry: &Entry) -> Tally {
+        self.total + self.pairs[entry.pair_index()]
+            - self.cards[entry.cards[0]]
+            - self.cards[entry.cards[1]]
+    }
+}
+
+// buffers reused across runouts
+struct Sweep {
+    entries: [Vec<Entry>; 2],
+    all: [CardRemovalTally; 2],
+    stronger: CardRemovalTally,
+    stronger_or_equal: CardRemovalTally,
+}
+
+impl Sweep {
+    fn new() -> Self {
+        Sweep {
+            entries: [vec![], vec![]],
+            all: [CardRemovalTally::new(), CardRemovalTally::new()],
+            stronger: CardRemovalTally::new(),
+            stronger_or_equal: CardRemovalTally::new(),
+        }
+    }
+
+    fn run(
+        &mut self,
+        board: &[Card; 5],
+        players: &[Vec<(CardPair, f64)>; 2],
+        accumulator: &mut EquityAccumulator,
+    ) {
+        let board_cards = CardSet::from(*board);
+        let evaluator = BoardEvaluator::new(board);
+
+        for (player, (entries, all)) in players
+            .iter()
+            .zip(self.entries.iter_mut().zip(self.all.iter_mut()))
+        {
+            all.clear(entries);
+            entries.clear();
+
+            for (card_pair, probability) in player.iter() {
+                if board_cards.contains(&card_pair[0]) || board_cards.contains(&card_pair[1]) {
+                    continue;
+                }
+
+                let (left, right) = (card_pair[0].index() as usize, card_pair[1].index() as usize);
+                let entry = Entry {
+                    cards: [left.min(right), left.max(right)],
+                    weight: *probability,
+                    power_index: evaluator.evaluate(card_pair).power_index(),
+                };
+
+                all.insert(&entry);
+                entries.push(entry);
+            }
+
+            entries.sort_unstable_by_key(|entry| entry.power_index);
+        }
+
+        let [hero_entries, villain_entries] = &self.entries;
+        let mut shares = [0.0; 2];
+        let mut exact_shares = [0; 2];
+        let mut total = 0.0;
+        let mut showdown_len = 0;
+        let mut is_exact = true;
+        let mut stronger_len = 0;
+        let mut stronger_or_equal_len = 0;
+
+        // hero's card pairs are visited from the strongest, so villain's card pairs stronger than
+        // the current one only increase
+        for hero in hero_entries {
+            while stronger_len < villain_entries.len()
+                && villain_entries[stronger_len].power_index < hero.power_index
+            {
+                self.stronger.insert(&villain_entries[stronger_len]);
+                stronger_len += 1;
+            }
+
+            while stronger_or_equal_len < villain_entries.len()
+                && villain_entries[stronger_or_equal_len].power_index <= hero.power_index
+            {
+                self.stronger_or_equal
+                    .insert(&villain_entries[stronger_or_equal_len]);
+                stronger_or_equal_len += 1;
+            }
+
+            let all = self.all[1].disjoint(hero);
+            let stronger = self.stronger.disjoint(hero);
+            let stronger_or_equal = self.stronger_or_equal.disjoint(hero);
+            let equal = stronger_or_equal - stronger;
+            let weaker = all - stronger_or_equal;
+
+            shares[0] += hero.weight * (weaker.weight + equal.weight / 2.0);
+            shares[1] += hero.weight * (stronger.weight + equal.weight / 2.0);
+            exact_shares[0] += 2 * weaker.count + equal.count;
+            exact_shares[1] += 2 * stronger.count + equal.count;
+            total += hero.weight * all.weight;
+            showdown_len += all.count;
+
+            if hero.weight != 1.0 && all.count > 0 {
+                is_exact = false;
+            }
+        }
+
+        if villain_entries
+            .iter()
+            .any(|villain| villain.weight != 1.0 && self.all[0].disjoint(villain).count > 0)
+        {
+            is_exact = false;
+        }
+
+        self.stronger.clear(&villain_entries[..stronger_len]);
+        self.stronger_or_equal
+            .clear(&villain_entries[..stronger_or_equal_len]);
+
+        let unit = accumulator.unit() / 2;
+        let exact_shares = [exact_shares[0] * unit, exact_shares[1] * unit];
+
+        accumulator.add_tally(
+            &shares,
+            is_exact.then_some(&exact_shares[..]),
+            total,
+            showdown_len,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{parse_board, Deck};
+    use crate::evaluator::FlopExhaustiveEvaluator;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::str::FromStr;
+
+    fn random_hand_range(rng: &mut ChaCha8Rng, is_weighted: bool) -> HandRange {
+        let cards = Card::all();
+        let mut card_pairs = vec![];
+
+        for i in 0..cards.len() {
+            for j in (i + 1)..cards.len() {
+                if rng.gen_bool(0.04) {
+                    let probability = if is_weighted {
+                        [0.25, 0.5, 0.75, 1.0][rng.gen_range(0..4)]
+                    } else {
+                        1.0
+                    };
+
+                    card_pairs.push((CardPair::new(cards[i], cards[j]), probability));
+                }
+            }
+        }
+
+        card_pairs.into_iter().collect()
+    }
+
+    fn assert_same_equities(board: &[Option<Card>; 5], players: &[HandRange; 2]) {
+        let mut heads_up = HeadsUpEvaluator::new(board, players).unwrap();
+        let mut generic = FlopExhaustiveEvaluator::new(board, players).unwrap();
+
+        heads_up.scope(0, 1, 3, 4);
+        generic.scope(0, 1, 3, 4);
+
+        let heads_up = heads_up.equities();
+        let generic = generic.equities();
+
+        assert_eq!(heads_up.showdown_len(), generic.showdown_len());
+        assert_eq!(heads_up.exact_equity(0), generic.exact_equity(0));
+        assert_eq!(heads_up.exact_equity(1), generic.exact_equity(1));
+        assert!((heads_up.total_probability() - generic.total_probability()).abs() < 1e-6);
+
+        for i in 0..2 {
+            assert!((heads_up.equity(i) - generic.equity(i)).abs() < 1e-9);
+        }
+    }
+
+    mod equities {
+        use super::*;
+
+        #[test]
+        fn it_returns_exact_equity_of_set_over_set() {
+            let players = [
+                HandRange::from_str("JsJh").unwrap(),
+                HandRange::from_str("5s5h").unwrap(),
+            ];
+            let equities = HeadsUpEvaluator::new(&parse_board("Jd5d2c").unwrap(), &players)
+                .unwrap()
+                .equities();
+
+            assert_eq!(equities.showdown_len(), 990);
+            assert_eq!(equities.exact_equity(0), Some((947, 990)));
+            assert_eq!(equities.exact_equity(1), Some((43, 990)));
+        }
+
+        #[test]
+        fn it_matches_generic_evaluator_on_overlapping_ranges() {
+            let players = [
+                HandRange::from_str("JJ+,AQs+,AKo").unwrap(),
+                HandRange::from_str("TT+,AJs+,KQs,AQo+").unwrap(),
+            ];
+
+            assert_same_equities(&parse_board("Qs8d2h").unwrap(), &players);
+        }
+
+        #[test]
+        fn it_matches_generic_evaluator_on_random_ranges() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+            for is_weighted in [false, true, false, true] {
+                let mut deck = Deck::new();
+
+                deck.shuffle(&mut rng);
+
+                let flop = deck.deal_n(3).unwrap();
+                let board = [Some(flop[0]), Some(flop[1]), Some(flop[2]), None, None];
+                let players = [
+                    random_hand_range(&mut rng, is_weighted),
+                    random_hand_range(&mut rng, is_weighted),
+                ];
+
+                assert_same_equities(&board, &players);
+            }
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use super::dp_table::{dp_ref, AS_FLUSH, AS_RAINBOW};
-use crate::card::{Card, Rank, Suit};
+use super::backend::{Backend, DefaultBackend};
+use crate::card::Card;
 
-#[derive(Debug, PartialEq, Eq, Ord, Copy, Clone)]
-pub struct MadeHand(u16);
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct MadeHand(pub(super) u16);
 
 impl MadeHand {
     pub fn power_index(&self) -> u16 {
@@ -11,33 +11,33 @@ impl MadeHand {
 
     pub fn hand_type(&self) -> MadeHandType {
         match self.0 {
-            0..=9 => MadeHandType::StraightFlush,
-            10..=165 => MadeHandType::Quads,
-            166..=321 => MadeHandType::FullHouse,
-            322..=1598 => MadeHandType::Flush,
-            1599..=1608 => MadeHandType::Straight,
-            1609..=2466 => MadeHandType::Trips,
-            2467..=3324 => MadeHandType::TwoPair,
-            3325..=6184 => MadeHandType::Pair,
+            1..=10 => MadeHandType::StraightFlush,
+            11..=166 => MadeHandType::Quads,
+            167..=322 => MadeHandType::FullHouse,
+            323..=1599 => MadeHandType::Flush,
+            1600..=1609 => MadeHandType::Straight,
+            1610..=2467 => MadeHandType::Trips,
+            2468..=3325 => MadeHandType::TwoPair,
+            3326..=6185 => MadeHandType::Pair,
             _ => MadeHandType::HighCard,
         }
     }
 }
 
-impl PartialOrd for MadeHand {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.power_index().partial_cmp(&other.power_index())
+impl TryFrom<u16> for MadeHand {
+    type Error = ();
+
+    fn try_from(power_index: u16) -> Result<Self, Self::Error> {
+        match power_index {
+            1..=7462 => Ok(MadeHand(power_index)),
+            _ => Err(()),
+        }
     }
 }
 
 impl From<[Card; 7]> for MadeHand {
     fn from(cards: [Card; 7]) -> Self {
-        let flash_suit = find_flush_suit(&cards);
-
-        match flash_suit {
-            Some(suit) => MadeHand(AS_FLUSH[hash_for_flush(&cards, &suit) as usize]),
-            _ => MadeHand(AS_RAINBOW[hash_for_rainbow(&cards) as usize]),
-        }
+        DefaultBackend::evaluate(&cards)
     }
 }
 
@@ -7068,101 +7068,69 @@ mod tests {
             assert_eq!(made_hand.power_index(), 5946);
         }
     }
-}
-
-fn find_flush_suit<'c>(cards: &[Card; 7]) -> Option<Suit> {
-    let mut suit_counts = [0; 4];
 
-    for card in cards {
-        let suit = card.suit();
-        let suit_index = u8::from(suit) as usize;
-
-        suit_counts[suit_index] += 1;
-
-        if suit_counts[suit_index] >= 5 {
-            return Some(*suit);
-        }
-    }
+    mod hand_type {
+        use super::*;
 
-    None
-}
+        #[test]
+        fn it_classifies_boundaries_of_each_hand_type() {
+            let hand_type = |power_index| MadeHand::try_from(power_index).unwrap().hand_type();
 
-fn hash_for_flush<'c>(cards: &[Card; 7], suit: &Suit) -> u16 {
-    let mut hash: u16 = 0;
-
-    for card in cards.iter() {
-        if card.suit() == suit {
-            hash += match card.rank() {
-                Rank::Ace => 0b1000000000000,
-                Rank::King => 0b100000000000,
-                Rank::Queen => 0b10000000000,
-                Rank::Jack => 0b1000000000,
-                Rank::Ten => 0b100000000,
-                Rank::Nine => 0b10000000,
-                Rank::Eight => 0b1000000,
-                Rank::Seven => 0b100000,
-                Rank::Six => 0b10000,
-                Rank::Five => 0b1000,
-                Rank::Four => 0b100,
-                Rank::Trey => 0b10,
-                Rank::Deuce => 0b1,
-            };
+            assert_eq!(hand_type(1), MadeHandType::StraightFlush);
+            assert_eq!(hand_type(10), MadeHandType::StraightFlush);
+            assert_eq!(hand_type(11), MadeHandType::Quads);
+            assert_eq!(hand_type(167), MadeHandType::FullHouse);
+            assert_eq!(hand_type(323), MadeHandType::Flush);
+            assert_eq!(hand_type(1600), MadeHandType::Straight);
+            assert_eq!(hand_type(1609), MadeHandType::Straight);
+            assert_eq!(hand_type(1610), MadeHandType::Trips);
+            assert_eq!(hand_type(2468), MadeHandType::TwoPair);
+            assert_eq!(hand_type(3326), MadeHandType::Pair);
+            assert_eq!(hand_type(6185), MadeHandType::Pair);
+            assert_eq!(hand_type(6186), MadeHandType::HighCard);
+            assert_eq!(hand_type(7462), MadeHandType::HighCard);
         }
-    }
 
-    hash
-}
-
-const RANKS: [Rank; 13] = [
-    Rank::Deuce,
-    Rank::Trey,
-    Rank::Four,
-    Rank::Five,
-    Rank::Six,
-    Rank::Seven,
-    Rank::Eight,
-    Rank::Nine,
-    Rank::Ten,
-    Rank::Jack,
-    Rank::Queen,
-    Rank::King,
-    Rank::Ace,
-];
-
-fn hash_for_rainbow<'c>(cards: &[Card; 7]) -> u16 {
-    let mut card_len_each_rank: [u8; 13] = [0; 13];
-    let mut remaining_card_len: u8 = 0;
-
-    for card in cards.iter() {
-        let rank_index = u8::from(card.rank()) as usize;
-
-        card_len_each_rank[rank_index] += 1;
-        remaining_card_len += 1;
-    }
-
-    let mut hash: u16 = 0;
-
-    for rank in RANKS {
-        let rank_index = u8::from(rank) as usize;
-        let len: u8 = card_len_each_rank[rank_index];
+        #[test]
+        fn it_classifies_wheel_straight_flush() {
+            let made_hand: MadeHand =
+                crate::card_array!["5s", "4s", "3s", "2s", "As", "Kd", "Kc"].into();
 
-        if len == 0 {
-            continue;
+            assert_eq!(made_hand.hand_type(), MadeHandType::StraightFlush);
         }
 
-        hash += dp_ref(len, &rank, remaining_card_len);
+        #[test]
+        fn it_classifies_weakest_hands_of_each_hand_type() {
+            let made_hand =
+                |cards: [&str; 7]| MadeHand::from(cards.map(|card| card.parse().unwrap()));
 
-        remaining_card_len -= len;
+            let quads = made_hand(["2s", "2h", "2d", "2c", "3s", "3h", "3d"]);
+            let full_house = made_hand(["2s", "2h", "2d", "3s", "3h", "4c", "5d"]);
+            let flush = made_hand(["7s", "5s", "4s", "3s", "2s", "3h", "2h"]);
+            let straight = made_hand(["5s", "4h", "3d", "2c", "As", "4d", "3c"]);
 
-        if remaining_card_len <= 0 {
-            break;
+            assert_eq!(
+                (quads.power_index(), quads.hand_type()),
+                (166, MadeHandType::Quads)
+            );
+            assert_eq!(
+                (full_house.power_index(), full_house.hand_type()),
+                (322, MadeHandType::FullHouse)
+            );
+            assert_eq!(
+                (flush.power_index(), flush.hand_type()),
+                (1599, MadeHandType::Flush)
+            );
+            assert_eq!(
+                (straight.power_index(), straight.hand_type()),
+                (1609, MadeHandType::Straight)
+            );
         }
     }
-
-    hash
 }
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MadeHandType {
     HighCard,
     Pair,
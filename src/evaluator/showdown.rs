@@ -1,66 +1,66 @@
 use super::MadeHand;
-use crate::card::Card;
+use crate::card::{Card, CardSet};
 use crate::hand_range::CardPair;
-use fxhash::FxBuildHasher;
-use std::collections::HashSet;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Showdown {
     board: [Card; 5],
     players: Vec<ShowdownPlayer>,
-    probability: f32,
+    probability: f64,
 }
 
 impl Showdown {
-    pub fn new(players: Vec<CardPair>, board: [Card; 5], probability: f32) -> Option<Showdown> {
-        debug_assert!(board.len() == 5);
-
-        let mut showdown_players = vec![];
-        let mut strongest_index = u16::MAX;
-        let mut winner_indexes =
-            HashSet::with_capacity_and_hasher(players.len(), FxBuildHasher::default());
-
-        for (i, player) in players.into_iter().enumerate() {
-            if board.contains(&player[0]) || board.contains(&player[1]) {
-                return None;
-            }
+    pub fn new(players: Vec<CardPair>, board: [Card; 5], probability: f64) -> Option<Showdown> {
+        let board_cards = CardSet::from(board);
+
+        if players
+            .iter()
+            .any(|player| !board_cards.is_disjoint(&CardSet::from(player)))
+        {
+            return None;
+        }
 
-            let made_hand: MadeHand = [
-                player[0], player[1], board[0], board[1], board[2], board[3], board[4],
-            ]
-            .into();
-            let power_index = made_hand.power_index();
-
-            let showdown_player = ShowdownPlayer {
-                hole_cards: player,
-                board: [board[0], board[1], board[2], board[3], board[4]],
-                hand: made_hand,
-                win: false,
-            };
-
-            if power_index <= strongest_index {
-                if power_index < strongest_index {
-                    strongest_index = power_index;
-                    winner_indexes.clear();
-                }
-
-                winner_indexes.insert(i);
-            }
+        let players = players
+            .into_iter()
+            .map(|player| {
+                let hand = MadeHand::from([
+                    player[0], player[1], board[0], board[1], board[2], board[3], board[4],
+                ]);
 
-            showdown_players.push(showdown_player);
-        }
+                (player, hand)
+            })
+            .collect::<Vec<(CardPair, MadeHand)>>();
 
-        for (i, player) in showdown_players.iter_mut().enumerate() {
-            if winner_indexes.contains(&i) {
-                player.win = true;
-            }
-        }
+        Some(Showdown::from_hands(&players, board, probability))
+    }
 
-        Some(Showdown {
-            players: showdown_players,
+    // the caller guarantees that no hole cards collide with the board and that the hands are
+    // the ones made with the board
+    pub(crate) fn from_hands(
+        players: &[(CardPair, MadeHand)],
+        board: [Card; 5],
+        probability: f64,
+    ) -> Showdown {
+        let strongest_index = players
+            .iter()
+            .map(|(_, hand)| hand.power_index())
+            .min()
+            .unwrap_or(u16::MAX);
+
+        Showdown {
             board,
+            players: players
+                .iter()
+                .map(|(hole_cards, hand)| ShowdownPlayer {
+                    hole_cards: *hole_cards,
+                    board,
+                    hand: *hand,
+                    win: hand.power_index() == strongest_index,
+                })
+                .collect(),
             probability,
-        })
+        }
     }
 
     pub fn board(&self) -> &[Card; 5] {
@@ -71,7 +71,7 @@ impl Showdown {
         &self.players
     }
 
-    pub fn probability(&self) -> f32 {
+    pub fn probability(&self) -> f64 {
         self.probability
     }
 
@@ -89,6 +89,7 @@ impl Showdown {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShowdownPlayer {
     hole_cards: CardPair,
     board: [Card; 5],
@@ -1,9 +1,33 @@
+mod blockers;
 mod card_pair;
+#[allow(clippy::module_inception)]
 mod hand_range;
 mod hand_range_token;
+mod range_format;
+mod range_grid;
 mod rank_pair;
+mod sampler;
+mod statistics;
+mod validation;
 
+pub use blockers::{BlockedRankPair, Blockers};
 pub use card_pair::CardPair;
-pub use hand_range::HandRange;
-pub use hand_range_token::{HandRangeToken, HandRangeTokenKind};
+pub use hand_range::{HandRange, HandRangeIterator, ParseHandRangeError};
+pub use hand_range_token::{HandRangeToken, HandRangeTokenKind, ParseHandRangeTokenError};
+pub use range_format::{ParseRangeFormatError, RangeFormat, WriteRangeFormatError};
+pub use range_grid::{RangeGrid, RangeGridCell};
 pub use rank_pair::RankPair;
+pub use sampler::HandRangeSampler;
+pub use validation::InvalidHandRangeError;
+
+// hash maps with std. b-tree maps stand in for them without std, since neither the hash maps of
+// std nor the hasher of fxhash are available there.
+#[cfg(feature = "std")]
+pub type Map<K, V> = std::collections::HashMap<K, V, fxhash::FxBuildHasher>;
+#[cfg(not(feature = "std"))]
+pub type Map<K, V> = alloc::collections::BTreeMap<K, V>;
+
+#[cfg(feature = "std")]
+type Set<T> = std::collections::HashSet<T, fxhash::FxBuildHasher>;
+#[cfg(not(feature = "std"))]
+type Set<T> = alloc::collections::BTreeSet<T>;
@@ -0,0 +1,192 @@
+use super::{CardPair, HandRange, Map, RankPair};
+use crate::card::CardSet;
+use alloc::{vec, vec::Vec};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BlockedRankPair {
+    rank_pair: RankPair,
+    combos: f32,
+    blocked_combos: f32,
+}
+
+impl BlockedRankPair {
+    pub fn rank_pair(&self) -> RankPair {
+        self.rank_pair
+    }
+
+    // weighted combos in the range before removing the blocked ones
+    pub fn combos(&self) -> f32 {
+        self.combos
+    }
+
+    pub fn blocked_combos(&self) -> f32 {
+        self.blocked_combos
+    }
+
+    pub fn remaining_combos(&self) -> f32 {
+        self.combos - self.blocked_combos
+    }
+
+    // zero when the rank pair has no weighted combos to block
+    pub fn blocked_ratio(&self) -> f32 {
+        if self.combos == 0.0 {
+            0.0
+        } else {
+            self.blocked_combos / self.combos
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Blockers {
+    range: HandRange,
+    rank_pairs: Vec<BlockedRankPair>,
+    // indexes of `rank_pairs` by their rank pair
+    indexes: Map<RankPair, usize>,
+}
+
+impl Blockers {
+    // the range conditioned on the blocking cards, i.e. without the combos that share a card
+    pub fn range(&self) -> &HandRange {
+        &self.range
+    }
+
+    // every rank pair of the original range, in the order of their strongest card pair
+    pub fn rank_pairs(&self) -> &[BlockedRankPair] {
+        &self.rank_pairs
+    }
+
+    pub fn get(&self, rank_pair: &RankPair) -> Option<&BlockedRankPair> {
+        self.indexes
+            .get(rank_pair)
+            .map(|index| &self.rank_pairs[*index])
+    }
+
+    pub fn blocked_combos(&self) -> f32 {
+        self.rank_pairs
+            .iter()
+            .map(|blocked| blocked.blocked_combos)
+            .sum()
+    }
+}
+
+impl HandRange {
+    // how holding the card pair removes combos from this range. useful for measuring the blocker
+    // effect of hero's hole cards on villain's range.
+    pub fn blocked_by(&self, card_pair: &CardPair) -> Blockers {
+        let cards = CardSet::from(card_pair);
+        let mut rank_pairs: Vec<BlockedRankPair> = vec![];
+        let mut indexes: Map<RankPair, usize> = Map::default();
+
+        for (card_pair, probability) in self.iter() {
+            let rank_pair = RankPair::from(card_pair);
+            let blocked_combos = if cards.is_disjoint(&CardSet::from(card_pair)) {
+                0.0
+            } else {
+                *probability
+            };
+
+            match indexes.get(&rank_pair) {
+                Some(index) => {
+                    let blocked = &mut rank_pairs[*index];
+
+                    blocked.combos += probability;
+                    blocked.blocked_combos += blocked_combos;
+                }
+                None => {
+                    indexes.insert(rank_pair, rank_pairs.len());
+                    rank_pairs.push(BlockedRankPair {
+                        rank_pair,
+                        combos: *probability,
+                        blocked_combos,
+                    });
+                }
+            }
+        }
+
+        Blockers {
+            range: self.excluding(&cards),
+            rank_pairs,
+            indexes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Rank;
+    use std::str::FromStr;
+
+    mod blocked_by {
+        use super::*;
+
+        #[test]
+        fn it_removes_combos_sharing_a_card() {
+            let blockers = HandRange::from_str("AA,KK,AKs:0.5")
+                .unwrap()
+                .blocked_by(&CardPair::from_str("As7h").unwrap());
+
+            assert_eq!(
+                blockers.range(),
+                &HandRange::from_str("AhAd,AhAc,AdAc,KK,AhKh:0.5,AdKd:0.5,AcKc:0.5").unwrap()
+            );
+            assert_eq!(blockers.blocked_combos(), 3.5);
+        }
+
+        #[test]
+        fn it_summarizes_blocked_combos_by_rank_pair() {
+            let blockers = HandRange::from_str("AA,KK,AKs:0.5")
+                .unwrap()
+                .blocked_by(&CardPair::from_str("As7h").unwrap());
+            let pocket_aces = blockers.get(&RankPair::Pocket(Rank::Ace)).unwrap();
+            let suited_ace_king = blockers
+                .get(&RankPair::Suited(Rank::Ace, Rank::King))
+                .unwrap();
+            let pocket_kings = blockers.get(&RankPair::Pocket(Rank::King)).unwrap();
+
+            assert_eq!(pocket_aces.combos(), 6.0);
+            assert_eq!(pocket_aces.blocked_combos(), 3.0);
+            assert_eq!(pocket_aces.blocked_ratio(), 0.5);
+            assert_eq!(suited_ace_king.combos(), 2.0);
+            assert_eq!(suited_ace_king.remaining_combos(), 1.5);
+            assert_eq!(suited_ace_king.blocked_ratio(), 0.25);
+            assert_eq!(pocket_kings.blocked_combos(), 0.0);
+            assert_eq!(blockers.get(&RankPair::Pocket(Rank::Seven)), None);
+        }
+
+        #[test]
+        fn it_returns_zero_ratio_without_weighted_combos() {
+            let blockers = HandRange::from_str("AA:0,KK")
+                .unwrap()
+                .blocked_by(&CardPair::from_str("As7h").unwrap());
+            let pocket_aces = blockers.get(&RankPair::Pocket(Rank::Ace)).unwrap();
+
+            assert_eq!(pocket_aces.combos(), 0.0);
+            assert_eq!(pocket_aces.blocked_ratio(), 0.0);
+        }
+
+        #[test]
+        fn it_lists_rank_pairs_in_order() {
+            let blockers = HandRange::from_str("72o,KK,AKs,AA")
+                .unwrap()
+                .blocked_by(&CardPair::from_str("KsKh").unwrap());
+
+            assert_eq!(
+                blockers
+                    .rank_pairs()
+                    .iter()
+                    .map(|blocked| blocked.rank_pair().to_string())
+                    .collect::<Vec<String>>(),
+                vec!["AA", "AKs", "KK", "72o"]
+            );
+            assert_eq!(
+                blockers
+                    .get(&RankPair::Pocket(Rank::King))
+                    .unwrap()
+                    .remaining_combos(),
+                1.0
+            );
+        }
+    }
+}
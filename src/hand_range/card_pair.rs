@@ -1,9 +1,10 @@
-use crate::card::Card;
-use std::fmt::{Debug, Display, Formatter};
-use std::ops::Index;
-use std::str::FromStr;
+use crate::card::{tokenize_cards, Card, CardSet, CardToken, Notate, Notation};
+use alloc::string::{String, ToString};
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::Index;
+use core::str::FromStr;
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct CardPair(Card, Card);
 
 impl CardPair {
@@ -16,6 +17,18 @@ impl CardPair {
     }
 }
 
+impl From<&CardPair> for CardSet {
+    fn from(card_pair: &CardPair) -> Self {
+        CardSet::from([card_pair.0, card_pair.1])
+    }
+}
+
+impl From<CardPair> for CardSet {
+    fn from(card_pair: CardPair) -> Self {
+        CardSet::from(&card_pair)
+    }
+}
+
 impl Index<usize> for CardPair {
     type Output = Card;
 
@@ -29,30 +42,47 @@ impl Index<usize> for CardPair {
 }
 
 impl Display for CardPair {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}{}", self.0, self.1)
     }
 }
 
 impl Debug for CardPair {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "CardPair({}{})", self.0, self.1)
     }
 }
 
+impl Notate for CardPair {
+    fn fmt_with(&self, f: &mut Formatter, notation: Notation) -> core::fmt::Result {
+        self.0.fmt_with(f, notation)?;
+        self.1.fmt_with(f, notation)
+    }
+}
+
 impl FromStr for CardPair {
     type Err = ParseCardPairError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        if value.len() != 4 {
+        let tokens = tokenize_cards(value);
+
+        if tokens.len() != 2 {
             return Err(Self::Err::InvalidLength(value.len()));
         }
 
-        match (Card::from_str(&value[0..2]), Card::from_str(&value[2..4])) {
-            (Ok(l), Ok(r)) => Ok(CardPair::new(l, r)),
-            (Err(_), _) => Err(Self::Err::InvalidCardStr((&value[0..2]).to_string())),
-            (Ok(_), Err(_)) => Err(Self::Err::InvalidCardStr((&value[2..4]).to_string())),
+        let mut cards = [None; 2];
+
+        for (i, token) in tokens.into_iter().enumerate() {
+            cards[i] = match token {
+                CardToken::Card(card_str) => Some(
+                    Card::from_str(card_str)
+                        .map_err(|_| Self::Err::InvalidCardStr(card_str.to_string()))?,
+                ),
+                CardToken::Wildcard => return Err(Self::Err::InvalidCardStr("x".to_string())),
+            };
         }
+
+        Ok(CardPair::new(cards[0].unwrap(), cards[1].unwrap()))
     }
 }
 
@@ -60,6 +90,36 @@ impl FromStr for CardPair {
 mod tests {
     use super::*;
 
+    mod card_set_from_card_pair {
+        use super::*;
+
+        #[test]
+        fn it_turns_into_card_set() {
+            assert_eq!(
+                CardSet::from(CardPair::from_str("AsKc").unwrap()),
+                CardSet::from_str("AsKc").unwrap()
+            );
+        }
+    }
+
+    mod notate {
+        use super::*;
+        use crate::card::{Rank, Suit};
+
+        #[test]
+        fn it_formats_in_unicode() {
+            assert_eq!(
+                CardPair::new(
+                    Card::new(Rank::Ace, Suit::Spade),
+                    Card::new(Rank::Ten, Suit::Heart),
+                )
+                .notate(Notation::Unicode)
+                .to_string(),
+                "A♠T♥"
+            );
+        }
+    }
+
     mod tests_from_str {
         use super::*;
         use crate::card::{Rank, Suit};
@@ -75,6 +135,17 @@ mod tests {
             );
         }
 
+        #[test]
+        fn it_parses_str_in_alternate_notation() {
+            assert_eq!(
+                CardPair::from_str("A♠10h").unwrap(),
+                CardPair::new(
+                    Card::new(Rank::Ace, Suit::Spade),
+                    Card::new(Rank::Ten, Suit::Heart),
+                )
+            );
+        }
+
         #[test]
         fn it_returns_error_when_invalid_length() {
             assert_eq!(
@@ -1,37 +1,78 @@
+use super::hand_range_token::shift_down;
 use super::{CardPair, RankPair};
-use crate::card::{Card, Rank, RankRange, Suit, SuitRange};
-use crate::hand_range::{HandRangeToken, HandRangeTokenKind};
-use fxhash::FxBuildHasher;
-use std::collections::{hash_map, HashMap};
-use std::fmt::Display;
-use std::str::FromStr;
+use crate::card::{Card, CardSet, Notate, Notation, Rank, RankRange, Suit, SuitRange};
+use crate::hand_range::{HandRangeToken, HandRangeTokenKind, ParseHandRangeTokenError};
+use crate::hand_range::{Map, Set};
+use alloc::string::{String, ToString};
+use alloc::{vec, vec::Vec};
+use core::fmt::Display;
+use core::ops::Range;
+use core::str::FromStr;
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct HandRange(HashMap<CardPair, f32, FxBuildHasher>);
+pub struct HandRange {
+    card_pairs: Map<CardPair, f32>,
+    // the same card pairs in ascending order. a range never changes once it's built, so they're
+    // sorted only once instead of on every `iter()`.
+    sorted: Vec<(CardPair, f32)>,
+}
 
 impl HandRange {
+    fn new(card_pairs: Map<CardPair, f32>) -> HandRange {
+        let mut sorted = card_pairs
+            .iter()
+            .map(|(card_pair, probability)| (*card_pair, *probability))
+            .collect::<Vec<(CardPair, f32)>>();
+
+        sorted.sort_unstable_by_key(|(card_pair, _)| *card_pair);
+
+        HandRange { card_pairs, sorted }
+    }
+
     pub fn empty() -> HandRange {
-        HandRange(HashMap::with_hasher(FxBuildHasher::default()))
+        HandRange::new(Map::default())
     }
 
-    pub fn card_pairs(&self) -> &HashMap<CardPair, f32, FxBuildHasher> {
-        &self.0
+    pub fn card_pairs(&self) -> &Map<CardPair, f32> {
+        &self.card_pairs
     }
 
-    pub fn rank_pairs(&self) -> HashMap<RankPair, f32, FxBuildHasher> {
-        let mut rank_pairs = HashMap::with_hasher(FxBuildHasher::default());
+    pub fn get(&self, card_pair: &CardPair) -> Option<f32> {
+        self.card_pairs.get(card_pair).copied()
+    }
+
+    pub fn contains(&self, card_pair: &CardPair) -> bool {
+        self.card_pairs.contains_key(card_pair)
+    }
+
+    pub fn len(&self) -> usize {
+        self.card_pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.card_pairs.is_empty()
+    }
+
+    // card pairs in ascending order. unlike `card_pairs()`, the order doesn't depend on the hash
+    // layout so that it's reproducible across runs and machines.
+    pub fn iter(&self) -> HandRangeIterator<'_> {
+        HandRangeIterator(self.sorted.iter())
+    }
+
+    pub fn rank_pairs(&self) -> Map<RankPair, f32> {
+        let mut rank_pairs = Map::default();
 
         for rank in RankRange::all() {
             let example_pocket =
                 CardPair::new(Card::new(rank, Suit::Spade), Card::new(rank, Suit::Heart));
 
-            if self.0.contains_key(&example_pocket) {
+            if self.card_pairs.contains_key(&example_pocket) {
                 let pocket = RankPair::Pocket(rank);
-                let probability = self.0.get(&example_pocket).unwrap();
+                let probability = self.card_pairs.get(&example_pocket).unwrap();
 
                 if pocket
                     .into_iter()
-                    .all(|cp| self.0.get(&cp).is_some_and(|p| p == probability))
+                    .all(|cp| self.card_pairs.get(&cp).is_some_and(|p| p == probability))
                 {
                     rank_pairs.insert(pocket, *probability);
                 }
@@ -43,13 +84,13 @@ impl HandRange {
                 let example_suited =
                     CardPair::new(Card::new(high, Suit::Spade), Card::new(kicker, Suit::Spade));
 
-                if self.0.contains_key(&example_suited) {
+                if self.card_pairs.contains_key(&example_suited) {
                     let suited = RankPair::Suited(high, kicker);
-                    let probability = self.0.get(&example_suited).unwrap();
+                    let probability = self.card_pairs.get(&example_suited).unwrap();
 
                     if suited
                         .into_iter()
-                        .all(|cp| self.0.get(&cp).is_some_and(|p| p == probability))
+                        .all(|cp| self.card_pairs.get(&cp).is_some_and(|p| p == probability))
                     {
                         rank_pairs.insert(suited, *probability);
                     }
@@ -58,13 +99,13 @@ impl HandRange {
                 let example_ofsuit =
                     CardPair::new(Card::new(high, Suit::Spade), Card::new(kicker, Suit::Heart));
 
-                if self.0.contains_key(&example_ofsuit) {
+                if self.card_pairs.contains_key(&example_ofsuit) {
                     let ofsuit = RankPair::Ofsuit(high, kicker);
-                    let probability = self.0.get(&example_ofsuit).unwrap();
+                    let probability = self.card_pairs.get(&example_ofsuit).unwrap();
 
                     if ofsuit
                         .into_iter()
-                        .all(|cp| self.0.get(&cp).is_some_and(|p| p == probability))
+                        .all(|cp| self.card_pairs.get(&cp).is_some_and(|p| p == probability))
                     {
                         rank_pairs.insert(ofsuit, *probability);
                     }
@@ -75,10 +116,17 @@ impl HandRange {
         rank_pairs
     }
 
+    pub fn excluding(&self, cards: &CardSet) -> HandRange {
+        self.iter()
+            .filter(|(card_pair, _)| cards.is_disjoint(&CardSet::from(*card_pair)))
+            .map(|(card_pair, probability)| (*card_pair, *probability))
+            .collect()
+    }
+
     // TODO:
     // this logic sucks. we gotta revisit and rewrite in some appropriate way.
-    pub fn orphan_card_pairs(&self) -> HashMap<CardPair, f32, FxBuildHasher> {
-        let mut clone = self.0.clone();
+    pub fn orphan_card_pairs(&self) -> Map<CardPair, f32> {
+        let mut clone = self.card_pairs.clone();
         let rank_pairs = self.rank_pairs();
 
         for (rank_pair, _) in rank_pairs {
@@ -94,15 +142,43 @@ impl HandRange {
 impl<'a> IntoIterator for &'a HandRange {
     type Item = (&'a CardPair, &'a f32);
 
-    type IntoIter = hash_map::Iter<'a, CardPair, f32>;
+    type IntoIter = <&'a Map<CardPair, f32> as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.iter()
+        self.card_pairs.iter()
     }
 }
 
-impl Display for HandRange {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+// card pairs of `HandRange::iter()` in ascending order
+#[derive(Debug, Clone)]
+pub struct HandRangeIterator<'a>(core::slice::Iter<'a, (CardPair, f32)>);
+
+impl<'a> Iterator for HandRangeIterator<'a> {
+    type Item = (&'a CardPair, &'a f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|(card_pair, probability)| (card_pair, probability))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for HandRangeIterator<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_back()
+            .map(|(card_pair, probability)| (card_pair, probability))
+    }
+}
+
+impl ExactSizeIterator for HandRangeIterator<'_> {}
+
+impl HandRange {
+    fn tokens(&self) -> Vec<HandRangeToken> {
         let rank_pairs = self.rank_pairs();
         let orphan_card_pairs = self.orphan_card_pairs();
         let mut tokens = vec![];
@@ -332,6 +408,46 @@ impl Display for HandRange {
             }
         }
 
+        let mut tokens = combine_connectors(tokens);
+
+        // card pairs that don't form a rank pair are described with wildcard tokens such as
+        // "KhXx", "AhXh" or "AsKx" when the whole group shares the same probability. larger
+        // groups are tried first. groups may overlap since they are given the same probability.
+        let mut covered = Set::default();
+        let wildcard_kinds = Card::all()
+            .into_iter()
+            .map(HandRangeTokenKind::CardWithAny)
+            .chain(Card::all().into_iter().flat_map(|card| {
+                SuitRange::all()
+                    .into_iter()
+                    .map(move |suit| HandRangeTokenKind::CardWithSuit(card, suit))
+            }))
+            .chain(Card::all().into_iter().flat_map(|card| {
+                RankRange::all()
+                    .into_iter()
+                    .map(move |rank| HandRangeTokenKind::CardWithRank(card, rank))
+            }));
+
+        for kind in wildcard_kinds {
+            let card_pairs = HandRangeToken::new(kind, 1.0)
+                .into_iter()
+                .map(|(card_pair, _)| card_pair)
+                .collect::<Vec<CardPair>>();
+
+            if let Some(probability) = self.card_pairs.get(&card_pairs[0]) {
+                if card_pairs
+                    .iter()
+                    .all(|cp| self.card_pairs.get(cp) == Some(probability))
+                    && card_pairs
+                        .iter()
+                        .any(|cp| orphan_card_pairs.contains_key(cp) && !covered.contains(cp))
+                {
+                    covered.extend(card_pairs);
+                    tokens.push(HandRangeToken::new(kind, *probability));
+                }
+            }
+        }
+
         for high_rank in RankRange::all() {
             for kicker_rank in RankRange::inclusive(high_rank, Rank::Deuce) {
                 for high_suit in SuitRange::all() {
@@ -343,61 +459,197 @@ impl Display for HandRange {
                         let probability = orphan_card_pairs.get(&pair);
 
                         if let Some(probability) = probability {
-                            tokens.push(HandRangeToken::new(
-                                HandRangeTokenKind::SingleCardPair(pair),
-                                *probability,
-                            ));
+                            if covered.insert(pair) {
+                                tokens.push(HandRangeToken::new(
+                                    HandRangeTokenKind::SingleCardPair(pair),
+                                    *probability,
+                                ));
+                            }
                         }
                     }
                 }
             }
         }
 
-        let mut res = f.write_str("");
-        let mut is_empty = true;
+        tokens
+    }
+}
+
+// rank pairs standing alone in their kicker runs (e.g. "T9s", "98s", "87s") are combined into a
+// connector range token (e.g. "T9s-87s") when they keep the same gap and probability.
+fn combine_connectors(tokens: Vec<HandRangeToken>) -> Vec<HandRangeToken> {
+    let mut combined = vec![];
+    let mut used = vec![false; tokens.len()];
 
-        for token in tokens {
-            if is_empty {
-                res = res.and(token.fmt(f));
-            } else {
-                res = res.and(write!(f, ",{}", token));
-            }
+    for i in 0..tokens.len() {
+        if used[i] {
+            continue;
+        }
 
-            is_empty = false;
+        let start = match tokens[i].kind() {
+            HandRangeTokenKind::SingleRankPair(
+                rank_pair @ (RankPair::Suited(..) | RankPair::Ofsuit(..)),
+            ) => *rank_pair,
+            kind => {
+                combined.push(HandRangeToken::new(*kind, tokens[i].probability()));
+
+                continue;
+            }
+        };
+        let probability = tokens[i].probability();
+        let mut end = start;
+
+        while let Some(next) = shift_down(&end) {
+            let found = (i + 1..tokens.len()).find(|j| {
+                !used[*j]
+                    && tokens[*j].kind() == &HandRangeTokenKind::SingleRankPair(next)
+                    && tokens[*j].probability() == probability
+            });
+
+            match found {
+                Some(j) => {
+                    used[j] = true;
+                    end = next;
+                }
+                None => break,
+            }
         }
 
-        res
+        let kind = match (start, end) {
+            _ if start == end => HandRangeTokenKind::SingleRankPair(start),
+            (
+                RankPair::Suited(Rank::Ace, Rank::King) | RankPair::Ofsuit(Rank::Ace, Rank::King),
+                _,
+            ) => HandRangeTokenKind::BottomClosedConnectorRange(end),
+            (_, RankPair::Suited(high, _) | RankPair::Ofsuit(high, _)) => {
+                HandRangeTokenKind::DoubleClosedConnectorRange(start, high)
+            }
+            _ => unreachable!(),
+        };
+
+        combined.push(HandRangeToken::new(kind, probability));
     }
+
+    combined
 }
 
-impl FromStr for HandRange {
-    type Err = ();
+impl Display for HandRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.fmt_with(f, Notation::Ascii)
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map = HashMap::with_hasher(FxBuildHasher::default());
-        let trimmed = s.replace(" ", "");
+impl Notate for HandRange {
+    fn fmt_with(&self, f: &mut core::fmt::Formatter, notation: Notation) -> core::fmt::Result {
+        for (i, token) in self.tokens().into_iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
 
-        if trimmed.len() == 0 {
-            return Ok(HandRange(HashMap::with_hasher(FxBuildHasher::default())));
+            token.fmt_with(f, notation)?;
         }
 
-        let haystacks = trimmed.split(",");
+        Ok(())
+    }
+}
+
+impl FromStr for HandRange {
+    type Err = ParseHandRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut map = Map::default();
+        let (normalized, spans) = normalize_notation(s);
+        // maps a byte range of the normalized string back to the one of `s`
+        let original_span = |span: Range<usize>| {
+            if span.is_empty() {
+                let at = span.start.checked_sub(1).map_or(0, |i| spans[i].end);
+
+                at..at
+            } else {
+                spans[span.start].start..spans[span.end - 1].end
+            }
+        };
+        let mut start = 0;
+
+        for h in normalized.split(',') {
+            let end = start + h.len();
+
+            // empty tokens coming from a trailing comma are tolerated
+            if !h.is_empty() {
+                let token = HandRangeToken::from_str(h).map_err(|error| {
+                    ParseHandRangeError::InvalidToken(
+                        s[original_span(start..end)].to_string(),
+                        error.map_span(|span| original_span(start + span.start..start + span.end)),
+                    )
+                })?;
 
-        for h in haystacks {
-            if let Ok(token) = HandRangeToken::from_str(h) {
                 for (card_pair, prob) in token {
                     map.insert(card_pair, prob);
                 }
             }
+
+            start = end + 1;
         }
 
-        Ok(HandRange(map))
+        Ok(HandRange::new(map))
     }
 }
 
+// the span of the error is the byte range of the whole range string
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseHandRangeError {
+    InvalidToken(String, ParseHandRangeTokenError),
+}
+
+impl Display for ParseHandRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ParseHandRangeError::InvalidToken(token, error) => {
+                write!(f, "{} is not a valid hand range token: {}", token, error)
+            }
+        }
+    }
+}
+
+// rewrites alternate notations of cards (e.g. "10h", "A♠") into the canonical ascii ones and
+// drops spaces so that range tokens can be parsed uniformly. the byte range of `value` that each
+// byte of the result comes from is returned along with it.
+fn normalize_notation(value: &str) -> (String, Vec<Range<usize>>) {
+    let mut normalized = String::with_capacity(value.len());
+    let mut spans = Vec::with_capacity(value.len());
+    let mut prev = None;
+    let mut chars = value.char_indices().filter(|(_, c)| *c != ' ').peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let is_ten = c == '1'
+            && chars.peek().map(|(_, c)| *c) == Some('0')
+            && !prev.is_some_and(|p: char| p.is_ascii_digit() || p == '.');
+
+        if is_ten {
+            let (j, _) = chars.next().unwrap();
+
+            normalized.push('T');
+            spans.push(i..j + 1);
+            prev = Some('0');
+
+            continue;
+        }
+
+        match Suit::try_from(c) {
+            Ok(suit) if !c.is_ascii() => normalized.push(char::from(suit)),
+            _ => normalized.push(c),
+        }
+
+        spans.resize(normalized.len(), i..i + c.len_utf8());
+        prev = Some(c);
+    }
+
+    (normalized, spans)
+}
+
 impl FromIterator<(CardPair, f32)> for HandRange {
     fn from_iter<T: IntoIterator<Item = (CardPair, f32)>>(iter: T) -> Self {
-        HandRange(iter.into_iter().collect())
+        HandRange::new(iter.into_iter().collect())
     }
 }
 
@@ -478,7 +730,7 @@ mod tests {
                     .chain(
                         RankRange::inclusive(Rank::Five, Rank::Four)
                             .into_iter()
-                            .flat_map(|r| RankPair::Pocket(r))
+                            .flat_map(RankPair::Pocket)
                     )
                     .collect::<HandRange>()
                     .to_string(),
@@ -495,7 +747,7 @@ mod tests {
                     .chain(
                         RankRange::inclusive(Rank::Ten, Rank::Eight)
                             .into_iter()
-                            .flat_map(|r| RankPair::Pocket(r))
+                            .flat_map(RankPair::Pocket)
                     )
                     .collect::<HandRange>()
                     .to_string(),
@@ -645,60 +897,62 @@ mod tests {
                 .into_iter()
                 .collect::<HandRange>()
                 .to_string(),
-                "JsJh,JsJd,JsJc,JsJh,JhJd,JhJc,JsJd,JhJd,JsJc,JhJc"
+                "JsJx,JhJx"
             );
         }
 
         #[test]
         fn it_formats_incomplete_pocket_jacks_with_different_prob() {
             assert_eq!(
-            [
-                (
-                    CardPair::new(
-                        Card::new(Rank::Jack, Suit::Spade),
-                        Card::new(Rank::Jack, Suit::Heart)
+                [
+                    (
+                        CardPair::new(
+                            Card::new(Rank::Jack, Suit::Spade),
+                            Card::new(Rank::Jack, Suit::Heart)
+                        ),
+                        0.5
                     ),
-                    0.5
-                ),
-                (
-                    CardPair::new(
-                        Card::new(Rank::Jack, Suit::Spade),
-                        Card::new(Rank::Jack, Suit::Diamond)
+                    (
+                        CardPair::new(
+                            Card::new(Rank::Jack, Suit::Spade),
+                            Card::new(Rank::Jack, Suit::Diamond)
+                        ),
+                        1.0
                     ),
-                    1.0
-                ),
-                (
-                    CardPair::new(
-                        Card::new(Rank::Jack, Suit::Spade),
-                        Card::new(Rank::Jack, Suit::Club)
+                    (
+                        CardPair::new(
+                            Card::new(Rank::Jack, Suit::Spade),
+                            Card::new(Rank::Jack, Suit::Club)
+                        ),
+                        0.5
                     ),
-                    0.5
-                ),
-                (
-                    CardPair::new(
-                        Card::new(Rank::Jack, Suit::Heart),
-                        Card::new(Rank::Jack, Suit::Diamond)
+                    (
+                        CardPair::new(
+                            Card::new(Rank::Jack, Suit::Heart),
+                            Card::new(Rank::Jack, Suit::Diamond)
+                        ),
+                        0.5
                     ),
-                    0.5
-                ),
-                (
-                    CardPair::new(
-                        Card::new(Rank::Jack, Suit::Heart),
-                        Card::new(Rank::Jack, Suit::Club)
+                    (
+                        CardPair::new(
+                            Card::new(Rank::Jack, Suit::Heart),
+                            Card::new(Rank::Jack, Suit::Club)
+                        ),
+                        0.5
                     ),
-                    0.5
-                ),
-                (
-                    CardPair::new(
-                        Card::new(Rank::Jack, Suit::Diamond),
-                        Card::new(Rank::Jack, Suit::Club)
+                    (
+                        CardPair::new(
+                            Card::new(Rank::Jack, Suit::Diamond),
+                            Card::new(Rank::Jack, Suit::Club)
+                        ),
+                        0.5
                     ),
-                    0.5
-                ),
-            ].into_iter().collect::<HandRange>()
-            .to_string(),
-            "JsJh:0.5,JsJd,JsJc:0.5,JsJh:0.5,JhJd:0.5,JhJc:0.5,JsJd,JhJd:0.5,JdJc:0.5,JsJc:0.5,JhJc:0.5,JdJc:0.5"
-        );
+                ]
+                .into_iter()
+                .collect::<HandRange>()
+                .to_string(),
+                "JhJx:0.5,JcJx:0.5,JsJd"
+            );
         }
 
         #[test]
@@ -1095,15 +1349,167 @@ mod tests {
         );
         }
 
+        #[test]
+        fn it_formats_suited_connectors_plus() {
+            assert_eq!(HandRange::from_str("54s+").unwrap().to_string(), "54s+");
+        }
+
+        #[test]
+        fn it_formats_suited_connectors_range() {
+            assert_eq!(
+                HandRange::from_str("T9s,98s,87s,76s,65s,54s")
+                    .unwrap()
+                    .to_string(),
+                "T9s-54s"
+            );
+        }
+
+        #[test]
+        fn it_formats_ofsuit_one_gappers_range_with_prob() {
+            assert_eq!(
+                HandRange::from_str("T8o-53o:0.5").unwrap().to_string(),
+                "T8o-53o:0.5"
+            );
+        }
+
+        #[test]
+        fn it_does_not_combine_connectors_in_kicker_runs() {
+            assert_eq!(
+                HandRange::from_str("KQs,QJs,JTs,J9s").unwrap().to_string(),
+                "KQs-QJs,J9s+"
+            );
+        }
+
+        #[test]
+        fn it_formats_card_with_any_card() {
+            assert_eq!(HandRange::from_str("KhXx").unwrap().to_string(), "KhXx");
+        }
+
+        #[test]
+        fn it_formats_card_with_suit() {
+            assert_eq!(
+                HandRange::from_str("AhXh:0.5").unwrap().to_string(),
+                "AhXh:0.5"
+            );
+        }
+
+        #[test]
+        fn it_formats_card_with_rank_next_to_rank_pair() {
+            assert_eq!(
+                HandRange::from_str("AKs,AsKh,AsKd,AsKc")
+                    .unwrap()
+                    .to_string(),
+                "AKs,AsKx"
+            );
+        }
+
+        #[test]
+        fn it_does_not_format_card_with_rank_when_prob_differs() {
+            assert_eq!(
+                HandRange::from_str("AsKs,AsKh,AsKd,AsKc:0.5")
+                    .unwrap()
+                    .to_string(),
+                "AsKs,AsKh,AsKd,AsKc:0.5"
+            );
+        }
+
         #[test]
         fn it_formats_empty_hand_range() {
             assert_eq!(
-                HandRange::from_iter(std::iter::empty::<CardPair>()).to_string(),
+                HandRange::from_iter(core::iter::empty::<CardPair>()).to_string(),
                 ""
             );
         }
     }
 
+    mod notate {
+        use super::*;
+
+        #[test]
+        fn it_formats_card_pairs_in_unicode() {
+            assert_eq!(
+                HandRange::from_str("QQ+,AKs,AsKh:0.5")
+                    .unwrap()
+                    .notate(Notation::Unicode)
+                    .to_string(),
+                "QQ+,AKs,A♠K♥:0.5"
+            );
+        }
+    }
+
+    mod iter {
+        use super::*;
+
+        #[test]
+        fn it_iterates_card_pairs_in_ascending_order() {
+            let card_pairs = HandRange::from_str("22,AKs,T9o")
+                .unwrap()
+                .iter()
+                .map(|(card_pair, _)| *card_pair)
+                .collect::<Vec<CardPair>>();
+
+            assert_eq!(card_pairs.len(), 22);
+            assert_eq!(card_pairs[0], CardPair::from_str("AsKs").unwrap());
+            assert_eq!(card_pairs[21], CardPair::from_str("2d2c").unwrap());
+            assert!(card_pairs.windows(2).all(|w| w[0] < w[1]));
+        }
+
+        #[test]
+        fn it_iterates_the_same_card_pairs_as_map() {
+            let hand_range = HandRange::from_str("QQ+,AKs:0.5,7d6h")
+                .unwrap()
+                .excluding(&CardSet::from_str("As").unwrap());
+            let mut unordered = (&hand_range).into_iter().collect::<Vec<_>>();
+
+            unordered.sort_unstable_by_key(|(card_pair, _)| **card_pair);
+
+            assert_eq!(hand_range.iter().len(), hand_range.len());
+            assert_eq!(hand_range.iter().collect::<Vec<_>>(), unordered);
+        }
+
+        #[test]
+        fn it_iterates_in_the_same_order_regardless_of_insertion_order() {
+            let forward = HandRange::from_str("QQ+,AKs:0.5,7d6h").unwrap();
+            let backward = forward
+                .iter()
+                .rev()
+                .map(|(card_pair, probability)| (*card_pair, *probability))
+                .collect::<HandRange>();
+
+            assert_eq!(
+                forward.iter().collect::<Vec<_>>(),
+                backward.iter().collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn it_looks_up_card_pairs() {
+            let hand_range = HandRange::from_str("AKs:0.5").unwrap();
+
+            assert_eq!(hand_range.len(), 4);
+            assert!(hand_range.contains(&CardPair::from_str("AhKh").unwrap()));
+            assert_eq!(
+                hand_range.get(&CardPair::from_str("AhKh").unwrap()),
+                Some(0.5)
+            );
+            assert_eq!(hand_range.get(&CardPair::from_str("AhKs").unwrap()), None);
+        }
+    }
+
+    mod excluding {
+        use super::*;
+
+        #[test]
+        fn it_removes_card_pairs_blocked_by_cards() {
+            assert_eq!(
+                HandRange::from_str("AA,AKs:0.5,KQo")
+                    .unwrap()
+                    .excluding(&CardSet::from_str("AsQh").unwrap()),
+                HandRange::from_str("AhAd,AhAc,AdAc,AhKh:0.5,AdKd:0.5,AcKc:0.5,KsQd,KsQc,KhQs,KhQd,KhQc,KdQs,KdQc,KcQs,KcQd").unwrap()
+            );
+        }
+    }
+
     mod from_str {
         use super::*;
 
@@ -1113,16 +1519,16 @@ mod tests {
                 HandRange::from_str("88-66,JJ+,44,AQs-A9s,98o-96o,K8s+,ATo+,44,JTs,72o,AsKs,7d6h")
                     .unwrap(),
                 HandRange::from_iter(
-                    std::iter::empty()
+                    core::iter::empty()
                         .chain(
                             RankRange::inclusive(Rank::Eight, Rank::Six)
                                 .into_iter()
-                                .flat_map(|r| RankPair::Pocket(r))
+                                .flat_map(RankPair::Pocket)
                         )
                         .chain(
                             RankRange::inclusive(Rank::Ace, Rank::Jack)
                                 .into_iter()
-                                .flat_map(|r| RankPair::Pocket(r))
+                                .flat_map(RankPair::Pocket)
                         )
                         .chain(
                             RankRange::inclusive(Rank::Queen, Rank::Nine)
@@ -1165,7 +1571,7 @@ mod tests {
                 HandRange::from_str("88-66:0.66,JJ+:0.5,44,AQs-A9s:0.2,98o-96o:0.999,K8s+:0.80,ATo+:1,44:0.44,JTs:0.25,72o:0.27,AsKs:0.4,7d6h:0.67")
                     .unwrap(),
                 HandRange::from_iter(
-                    std::iter::empty()
+                    core::iter::empty()
                         .chain(
                             RankRange::inclusive(Rank::Eight, Rank::Six)
                                 .into_iter()
@@ -1217,6 +1623,89 @@ mod tests {
             );
         }
 
+        #[test]
+        fn it_parses_str_in_alternate_notation() {
+            assert_eq!(
+                HandRange::from_str("A♠K♠:0.5, 10h9h, A10s:0.1").unwrap(),
+                HandRange::from_str("AsKs:0.5,Th9h,ATs:0.1").unwrap()
+            );
+        }
+
+        #[test]
+        fn it_parses_str_with_wildcards() {
+            assert_eq!(
+                HandRange::from_str("AsKx,AhXh:0.5").unwrap(),
+                HandRange::from_str(
+                    "AsKs,AsKh,AsKd,AsKc,AhKh:0.5,AhQh:0.5,AhJh:0.5,AhTh:0.5,Ah9h:0.5,Ah8h:0.5,Ah7h:0.5,Ah6h:0.5,Ah5h:0.5,Ah4h:0.5,Ah3h:0.5,Ah2h:0.5"
+                )
+                .unwrap()
+            );
+        }
+
+        #[test]
+        fn it_parses_str_rank_with_any_card() {
+            let hand_range = HandRange::from_str("Ax").unwrap();
+
+            assert_eq!(hand_range.card_pairs().len(), 192);
+            assert_eq!(hand_range, HandRange::from_str("A2s+,A2o+").unwrap());
+        }
+
+        #[test]
+        fn it_returns_error_when_token_is_invalid() {
+            assert_eq!(
+                HandRange::from_str("AA,KQx,QQ").unwrap_err(),
+                ParseHandRangeError::InvalidToken(
+                    "KQx".to_string(),
+                    ParseHandRangeTokenError::UnexpectedChar(5..6)
+                )
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_probability_is_above_one() {
+            assert_eq!(
+                HandRange::from_str("AA:1.5").unwrap_err(),
+                ParseHandRangeError::InvalidToken(
+                    "AA:1.5".to_string(),
+                    ParseHandRangeTokenError::InvalidProbability(3..6)
+                )
+            );
+        }
+
+        #[test]
+        fn it_returns_error_spanning_original_notation() {
+            assert_eq!(
+                HandRange::from_str("A♠K♠, 10h 9h-,QQ").unwrap_err(),
+                ParseHandRangeError::InvalidToken(
+                    "10h 9h-".to_string(),
+                    ParseHandRangeTokenError::UnexpectedChar(16..17)
+                )
+            );
+            assert_eq!(
+                HandRange::from_str("QQ, AK ,JJ").unwrap_err(),
+                ParseHandRangeError::InvalidToken(
+                    "AK".to_string(),
+                    ParseHandRangeTokenError::UnexpectedEnd(6..6)
+                )
+            );
+        }
+
+        #[test]
+        fn it_formats_error() {
+            assert_eq!(
+                HandRange::from_str("AA,T9s-53s").unwrap_err().to_string(),
+                "T9s-53s is not a valid hand range token: the hands at 3..10 don't make a range."
+            );
+        }
+
+        #[test]
+        fn it_ignores_trailing_comma() {
+            assert_eq!(
+                HandRange::from_str("AA,").unwrap(),
+                HandRange::from_str("AA").unwrap()
+            );
+        }
+
         #[test]
         fn it_parses_empty_str() {
             assert_eq!(HandRange::from_str("").unwrap(), HandRange::empty());
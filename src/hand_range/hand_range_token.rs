@@ -1,8 +1,9 @@
 use super::{CardPair, RankPair};
-use crate::card::{Rank, RankRange};
-use regex::Regex;
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use crate::card::{Card, Notate, Notation, Rank, RankRange, Suit, SuitRange};
+use alloc::{vec, vec::Vec};
+use core::fmt::{Display, Formatter};
+use core::ops::Range;
+use core::str::FromStr;
 
 #[derive(Debug, PartialEq)]
 pub struct HandRangeToken {
@@ -10,22 +11,52 @@ pub struct HandRangeToken {
     probability: f32,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum HandRangeTokenKind {
     BottomClosedRankPairRange(RankPair),
     DoubleClosedRankPairRange(RankPair, Rank),
     SingleRankPair(RankPair),
     SingleCardPair(CardPair),
+    // e.g. "54s+", both ranks slide up together until the high card reaches ace
+    BottomClosedConnectorRange(RankPair),
+    // e.g. "T9s-54s" or "T8s-53s", the rank is the high card of the last rank pair
+    DoubleClosedConnectorRange(RankPair, Rank),
+    // e.g. "AsKx", the card with any card of the rank
+    CardWithRank(Card, Rank),
+    // e.g. "AhXh", the card with any card of the suit
+    CardWithSuit(Card, Suit),
+    // e.g. "KhXx", any card pair containing the card
+    CardWithAny(Card),
+    // e.g. "Ax", the rank with any card of another rank
+    RankWithAny(Rank),
+    // e.g. "Axs"
+    SuitedRankWithAny(Rank),
+    // e.g. "Axo"
+    OfsuitRankWithAny(Rank),
 }
 
 impl HandRangeToken {
     pub fn new(kind: HandRangeTokenKind, probability: f32) -> HandRangeToken {
         HandRangeToken { kind, probability }
     }
+
+    pub fn kind(&self) -> &HandRangeTokenKind {
+        &self.kind
+    }
+
+    pub fn probability(&self) -> f32 {
+        self.probability
+    }
 }
 
 impl Display for HandRangeToken {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        self.fmt_with(f, Notation::Ascii)
+    }
+}
+
+impl Notate for HandRangeToken {
+    fn fmt_with(&self, f: &mut Formatter, notation: Notation) -> core::fmt::Result {
         let res = match self.kind {
             HandRangeTokenKind::BottomClosedRankPairRange(rank_pair) => {
                 write!(f, "{}+", rank_pair)
@@ -36,7 +67,34 @@ impl Display for HandRangeToken {
                 RankPair::Ofsuit(high, kicker) => write!(f, "{}{}o-{}{}o", high, kicker, high, end),
             },
             HandRangeTokenKind::SingleRankPair(rank_pair) => rank_pair.fmt(f),
-            HandRangeTokenKind::SingleCardPair(card_pair) => card_pair.fmt(f),
+            HandRangeTokenKind::SingleCardPair(card_pair) => card_pair.fmt_with(f, notation),
+            HandRangeTokenKind::BottomClosedConnectorRange(rank_pair) => {
+                write!(f, "{}+", rank_pair)
+            }
+            // tokens built by hand may end at a rank that the range never reaches, in which case
+            // there is no kicker to write
+            HandRangeTokenKind::DoubleClosedConnectorRange(start, end) => {
+                match (start, connector_kicker(&start, end)) {
+                    (RankPair::Suited(..), Some(kicker)) => {
+                        write!(f, "{}-{}{}s", start, end, kicker)
+                    }
+                    (_, Some(kicker)) => write!(f, "{}-{}{}o", start, end, kicker),
+                    (_, None) => write!(f, "{}-{}", start, end),
+                }
+            }
+            HandRangeTokenKind::CardWithRank(card, rank) => {
+                card.fmt_with(f, notation).and(write!(f, "{}x", rank))
+            }
+            HandRangeTokenKind::CardWithSuit(card, suit) => card
+                .fmt_with(f, notation)
+                .and(f.write_str("X"))
+                .and(suit.fmt_with(f, notation)),
+            HandRangeTokenKind::CardWithAny(card) => {
+                card.fmt_with(f, notation).and(f.write_str("Xx"))
+            }
+            HandRangeTokenKind::RankWithAny(rank) => write!(f, "{}x", rank),
+            HandRangeTokenKind::SuitedRankWithAny(rank) => write!(f, "{}xs", rank),
+            HandRangeTokenKind::OfsuitRankWithAny(rank) => write!(f, "{}xo", rank),
         };
 
         if self.probability == 1.0 {
@@ -50,7 +108,7 @@ impl Display for HandRangeToken {
 impl IntoIterator for HandRangeToken {
     type Item = (CardPair, f32);
 
-    type IntoIter = std::vec::IntoIter<(CardPair, f32)>;
+    type IntoIter = alloc::vec::IntoIter<(CardPair, f32)>;
 
     fn into_iter(self) -> Self::IntoIter {
         match self.kind {
@@ -122,157 +180,429 @@ impl IntoIterator for HandRangeToken {
                 .collect::<Vec<(CardPair, f32)>>()
                 .into_iter(),
             HandRangeTokenKind::SingleCardPair(card_pair) => {
-                std::iter::once((card_pair, self.probability))
+                core::iter::once((card_pair, self.probability))
+                    .collect::<Vec<(CardPair, f32)>>()
+                    .into_iter()
+            }
+            HandRangeTokenKind::BottomClosedConnectorRange(rank_pair) => {
+                let mut rank_pairs = vec![rank_pair];
+
+                while let Some(rank_pair) = rank_pairs.last().and_then(shift_up) {
+                    rank_pairs.push(rank_pair);
+                }
+
+                rank_pairs
+                    .into_iter()
+                    .rev()
+                    .flat_map(|rp| rp.into_iter().map(|cp| (cp, self.probability)))
+                    .collect::<Vec<(CardPair, f32)>>()
+                    .into_iter()
+            }
+            HandRangeTokenKind::DoubleClosedConnectorRange(start, end) => {
+                let mut rank_pairs = vec![];
+                let mut rank_pair = Some(start);
+
+                while let Some(rp) = rank_pair {
+                    rank_pairs.push(rp);
+                    rank_pair = match rp {
+                        RankPair::Suited(high, _) | RankPair::Ofsuit(high, _) if high != end => {
+                            shift_down(&rp)
+                        }
+                        _ => None,
+                    };
+                }
+
+                rank_pairs
+                    .into_iter()
+                    .flat_map(|rp| rp.into_iter().map(|cp| (cp, self.probability)))
                     .collect::<Vec<(CardPair, f32)>>()
                     .into_iter()
             }
+            HandRangeTokenKind::CardWithRank(card, rank) => {
+                card_pairs_with(card, |other| other.rank() == &rank, self.probability)
+            }
+            HandRangeTokenKind::CardWithSuit(card, suit) => {
+                card_pairs_with(card, |other| other.suit() == &suit, self.probability)
+            }
+            HandRangeTokenKind::CardWithAny(card) => {
+                card_pairs_with(card, |_| true, self.probability)
+            }
+            HandRangeTokenKind::RankWithAny(rank) => {
+                card_pairs_of_rank(rank, |_, _| true, self.probability)
+            }
+            HandRangeTokenKind::SuitedRankWithAny(rank) => card_pairs_of_rank(
+                rank,
+                |card, other| card.suit() == other.suit(),
+                self.probability,
+            ),
+            HandRangeTokenKind::OfsuitRankWithAny(rank) => card_pairs_of_rank(
+                rank,
+                |card, other| card.suit() != other.suit(),
+                self.probability,
+            ),
         }
     }
 }
 
-impl FromStr for HandRangeToken {
-    type Err = ();
+// the next stronger rank pair keeping the gap between the two ranks. e.g. 65s for 54s.
+pub(crate) fn shift_up(rank_pair: &RankPair) -> Option<RankPair> {
+    match rank_pair {
+        RankPair::Suited(high, kicker) => Some(RankPair::Suited(high.prev()?, kicker.prev()?)),
+        RankPair::Ofsuit(high, kicker) => Some(RankPair::Ofsuit(high.prev()?, kicker.prev()?)),
+        RankPair::Pocket(_) => None,
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let double_closed_pocket_pair_range_regex =
-            Regex::new(r"^[AKQJT98765432]{2}-[AKQJT98765432]{2}(:[01](\.[0-9]+)?)?$").unwrap();
-        let double_rank_pair_range_regex =
-            Regex::new(r"^[AKQJT98765432]{2}[so]-[AKQJT98765432]{2}[so](:[01](\.[0-9]+)?)?$")
-                .unwrap();
-        let bottom_closed_pocket_pair_range_regex =
-            Regex::new(r"^[AKQJT98765432]{2}\+(:[01](\.[0-9]+)?)?$").unwrap();
-        let bottom_closed_rank_pair_range_regex =
-            Regex::new(r"^[AKQJT98765432]{2}[so]\+(:[01](\.[0-9]+)?)?$").unwrap();
-        let single_pocket_pair_regex =
-            Regex::new(r"^[AKQJT98765432]{2}(:[01](\.[0-9]+)?)?$").unwrap();
-        let single_rank_pair_regex =
-            Regex::new(r"^[AKQJT98765432]{2}[so](:[01](\.[0-9]+)?)?$").unwrap();
-        let single_card_pair_regex =
-            Regex::new(r"^([AKQJT98765432][shdc]){2}(:[01](\.[0-9]+)?)?$").unwrap();
-
-        if double_closed_pocket_pair_range_regex.is_match(s)
-            && s[0..1] == s[1..2]
-            && s[3..4] == s[4..5]
-        {
-            if let (Ok(top), Ok(bottom)) = (Rank::from_str(&s[0..1]), Rank::from_str(&s[3..4])) {
-                return Ok(HandRangeToken::new(
-                    HandRangeTokenKind::DoubleClosedRankPairRange(RankPair::Pocket(top), bottom),
-                    parse_probability(&s[5..]),
-                ));
+// the next weaker rank pair keeping the gap between the two ranks. e.g. 43s for 54s.
+pub(crate) fn shift_down(rank_pair: &RankPair) -> Option<RankPair> {
+    match rank_pair {
+        RankPair::Suited(high, kicker) => Some(RankPair::Suited(high.next()?, kicker.next()?)),
+        RankPair::Ofsuit(high, kicker) => Some(RankPair::Ofsuit(high.next()?, kicker.next()?)),
+        RankPair::Pocket(_) => None,
+    }
+}
+
+// the kicker of the rank pair whose high card is `high` in the connector range starting at
+// `start`.
+fn connector_kicker(start: &RankPair, high: Rank) -> Option<Rank> {
+    let mut rank_pair = *start;
+
+    loop {
+        match rank_pair {
+            RankPair::Suited(h, kicker) | RankPair::Ofsuit(h, kicker) if h == high => {
+                return Some(kicker)
             }
+            _ => rank_pair = shift_down(&rank_pair)?,
         }
+    }
+}
 
-        if double_rank_pair_range_regex.is_match(s)
-            && s[0..1] == s[4..5]
-            && s[1..2] != s[5..6]
-            && s[2..3] == s[6..7]
-        {
-            if let (Ok(high), Ok(kicker_top), Ok(kicker_bottom)) = (
-                Rank::from_str(&s[0..1]),
-                Rank::from_str(&s[1..2]),
-                Rank::from_str(&s[5..6]),
-            ) {
-                if high < kicker_top && kicker_top < kicker_bottom {
-                    if &s[2..3] == "s" {
-                        return Ok(HandRangeToken::new(
-                            HandRangeTokenKind::DoubleClosedRankPairRange(
-                                RankPair::Suited(high, kicker_top),
-                                kicker_bottom,
-                            ),
-                            parse_probability(&s[7..]),
-                        ));
-                    }
+fn card_pairs_with<F: Fn(&Card) -> bool>(
+    card: Card,
+    predicate: F,
+    probability: f32,
+) -> alloc::vec::IntoIter<(CardPair, f32)> {
+    Card::all()
+        .into_iter()
+        .filter(|other| *other != card && predicate(other))
+        .map(|other| (CardPair::new(card, other), probability))
+        .collect::<Vec<(CardPair, f32)>>()
+        .into_iter()
+}
 
-                    return Ok(HandRangeToken::new(
-                        HandRangeTokenKind::DoubleClosedRankPairRange(
-                            RankPair::Ofsuit(high, kicker_top),
-                            kicker_bottom,
-                        ),
-                        parse_probability(&s[7..]),
-                    ));
-                }
+// pocket pairs are not included since the other card has to be of another rank.
+fn card_pairs_of_rank<F: Fn(&Card, &Card) -> bool>(
+    rank: Rank,
+    predicate: F,
+    probability: f32,
+) -> alloc::vec::IntoIter<(CardPair, f32)> {
+    let mut card_pairs = vec![];
+
+    for suit in SuitRange::all() {
+        let card = Card::new(rank, suit);
+
+        for other in Card::all() {
+            if other.rank() != &rank && predicate(&card, &other) {
+                card_pairs.push((CardPair::new(card, other), probability));
             }
         }
+    }
 
-        if bottom_closed_pocket_pair_range_regex.is_match(s) && s[0..1] == s[1..2] {
-            if let Ok(bottom) = Rank::from_str(&s[0..1]) {
-                return Ok(HandRangeToken::new(
-                    HandRangeTokenKind::BottomClosedRankPairRange(RankPair::Pocket(bottom)),
-                    parse_probability(&s[3..]),
-                ));
+    card_pairs.into_iter()
+}
+
+// the span of each error is the byte range of the token it points at
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseHandRangeTokenError {
+    UnexpectedChar(Range<usize>),
+    UnexpectedEnd(Range<usize>),
+    InvalidProbability(Range<usize>),
+    // well formed but not a range of hands, e.g. "T9s-53s"
+    InvalidHand(Range<usize>),
+}
+
+impl ParseHandRangeTokenError {
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            ParseHandRangeTokenError::UnexpectedChar(span)
+            | ParseHandRangeTokenError::UnexpectedEnd(span)
+            | ParseHandRangeTokenError::InvalidProbability(span)
+            | ParseHandRangeTokenError::InvalidHand(span) => span.clone(),
+        }
+    }
+
+    pub(crate) fn map_span<F: FnOnce(Range<usize>) -> Range<usize>>(self, f: F) -> Self {
+        match self {
+            ParseHandRangeTokenError::UnexpectedChar(span) => {
+                ParseHandRangeTokenError::UnexpectedChar(f(span))
+            }
+            ParseHandRangeTokenError::UnexpectedEnd(span) => {
+                ParseHandRangeTokenError::UnexpectedEnd(f(span))
+            }
+            ParseHandRangeTokenError::InvalidProbability(span) => {
+                ParseHandRangeTokenError::InvalidProbability(f(span))
+            }
+            ParseHandRangeTokenError::InvalidHand(span) => {
+                ParseHandRangeTokenError::InvalidHand(f(span))
             }
         }
+    }
+}
 
-        if bottom_closed_rank_pair_range_regex.is_match(s) && s[0..1] != s[1..2] {
-            if let (Ok(high), Ok(kicker_bottom)) =
-                (Rank::from_str(&s[0..1]), Rank::from_str(&s[1..2]))
-            {
-                if &s[2..3] == "s" {
-                    return Ok(HandRangeToken::new(
-                        HandRangeTokenKind::BottomClosedRankPairRange(RankPair::Suited(
-                            high,
-                            kicker_bottom,
-                        )),
-                        parse_probability(&s[4..]),
-                    ));
-                }
+impl Display for ParseHandRangeTokenError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            ParseHandRangeTokenError::UnexpectedChar(span) => {
+                write!(f, "unexpected character at {:?}.", span)
+            }
+            ParseHandRangeTokenError::UnexpectedEnd(span) => {
+                write!(f, "unexpected end at {}.", span.start)
+            }
+            ParseHandRangeTokenError::InvalidProbability(span) => {
+                write!(f, "the probability at {:?} is not between 0 and 1.", span)
+            }
+            ParseHandRangeTokenError::InvalidHand(span) => {
+                write!(f, "the hands at {:?} don't make a range.", span)
+            }
+        }
+    }
+}
 
-                return Ok(HandRangeToken::new(
-                    HandRangeTokenKind::BottomClosedRankPairRange(RankPair::Ofsuit(
-                        high,
-                        kicker_bottom,
-                    )),
-                    parse_probability(&s[4..]),
-                ));
+impl FromStr for HandRangeToken {
+    type Err = ParseHandRangeTokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { value: s, pos: 0 };
+        let kind = parser.kind()?;
+        let probability = parser.probability()?;
+
+        Ok(HandRangeToken::new(kind, probability))
+    }
+}
+
+// reads a token from left to right without backtracking
+struct Parser<'a> {
+    value: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.value.as_bytes().get(self.pos).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        let matched = self.peek() == Some(b);
+
+        if matched {
+            self.pos += 1;
+        }
+
+        matched
+    }
+
+    fn unexpected(&self) -> ParseHandRangeTokenError {
+        match self.value[self.pos..].chars().next() {
+            Some(c) => ParseHandRangeTokenError::UnexpectedChar(self.pos..self.pos + c.len_utf8()),
+            None => ParseHandRangeTokenError::UnexpectedEnd(self.pos..self.pos),
+        }
+    }
+
+    fn rank(&mut self) -> Result<Rank, ParseHandRangeTokenError> {
+        match self.peek() {
+            Some(b @ (b'A' | b'K' | b'Q' | b'J' | b'T' | b'2'..=b'9')) => {
+                self.pos += 1;
+
+                Ok(Rank::try_from(b as char).unwrap())
             }
+            _ => Err(self.unexpected()),
         }
+    }
 
-        if single_pocket_pair_regex.is_match(s) && s[0..1] == s[1..2] {
-            if let Ok(rank) = Rank::from_str(&s[0..1]) {
-                return Ok(HandRangeToken::new(
-                    HandRangeTokenKind::SingleRankPair(RankPair::Pocket(rank)),
-                    parse_probability(&s[2..]),
-                ));
+    fn suit(&mut self) -> Result<Suit, ParseHandRangeTokenError> {
+        match self.peek() {
+            Some(b @ (b's' | b'h' | b'd' | b'c')) => {
+                self.pos += 1;
+
+                Ok(Suit::try_from(b as char).unwrap())
             }
+            _ => Err(self.unexpected()),
         }
+    }
 
-        if single_rank_pair_regex.is_match(s) && s[0..1] != s[1..2] {
-            if let (Ok(high), Ok(kicker)) = (Rank::from_str(&s[0..1]), Rank::from_str(&s[1..2])) {
-                if &s[2..3] == "s" {
-                    return Ok(HandRangeToken::new(
-                        HandRangeTokenKind::SingleRankPair(RankPair::Suited(high, kicker)),
-                        parse_probability(&s[3..]),
-                    ));
-                }
+    fn is_wildcard(&self) -> bool {
+        matches!(self.peek(), Some(b'X' | b'x'))
+    }
 
-                return Ok(HandRangeToken::new(
-                    HandRangeTokenKind::SingleRankPair(RankPair::Ofsuit(high, kicker)),
-                    parse_probability(&s[3..]),
-                ));
+    fn kind(&mut self) -> Result<HandRangeTokenKind, ParseHandRangeTokenError> {
+        let rank = self.rank()?;
+
+        if self.is_wildcard() {
+            self.pos += 1;
+
+            return Ok(if self.eat(b's') {
+                HandRangeTokenKind::SuitedRankWithAny(rank)
+            } else if self.eat(b'o') {
+                HandRangeTokenKind::OfsuitRankWithAny(rank)
+            } else {
+                HandRangeTokenKind::RankWithAny(rank)
+            });
+        }
+
+        if matches!(self.peek(), Some(b's' | b'h' | b'd' | b'c')) {
+            let card = Card::new(rank, self.suit()?);
+
+            return self.card_with(card);
+        }
+
+        let kicker = self.rank()?;
+
+        self.rank_pair_range(rank, kicker)
+    }
+
+    fn card_with(&mut self, card: Card) -> Result<HandRangeTokenKind, ParseHandRangeTokenError> {
+        if self.is_wildcard() {
+            self.pos += 1;
+
+            if self.eat(b'x') {
+                return Ok(HandRangeTokenKind::CardWithAny(card));
             }
+
+            return Ok(HandRangeTokenKind::CardWithSuit(card, self.suit()?));
+        }
+
+        let rank = self.rank()?;
+
+        if self.eat(b'x') {
+            return Ok(HandRangeTokenKind::CardWithRank(card, rank));
+        }
+
+        let other = Card::new(rank, self.suit()?);
+
+        Ok(HandRangeTokenKind::SingleCardPair(CardPair::new(
+            card, other,
+        )))
+    }
+
+    // the suitedness of the rank pair, which pocket pairs don't have
+    fn rank_pair(
+        &mut self,
+        high: Rank,
+        kicker: Rank,
+    ) -> Result<RankPair, ParseHandRangeTokenError> {
+        if high == kicker {
+            Ok(RankPair::Pocket(high))
+        } else if self.eat(b's') {
+            Ok(RankPair::Suited(high, kicker))
+        } else if self.eat(b'o') {
+            Ok(RankPair::Ofsuit(high, kicker))
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn rank_pair_range(
+        &mut self,
+        high: Rank,
+        kicker: Rank,
+    ) -> Result<HandRangeTokenKind, ParseHandRangeTokenError> {
+        // ranks may come in either order, e.g. "KAs" is the same as "AKs"
+        let (high, kicker) = (high.min(kicker), high.max(kicker));
+        let start = self.rank_pair(high, kicker)?;
+
+        if self.eat(b'+') {
+            // connectors like "54s+" slide both ranks up instead of the kicker only
+            return Ok(match start {
+                RankPair::Suited(..) | RankPair::Ofsuit(..) if high.next() == Some(kicker) => {
+                    HandRangeTokenKind::BottomClosedConnectorRange(start)
+                }
+                _ => HandRangeTokenKind::BottomClosedRankPairRange(start),
+            });
         }
 
-        if single_card_pair_regex.is_match(s) {
-            if let Ok(card_pair) = (&s[0..4]).parse::<CardPair>() {
-                return Ok(HandRangeToken::new(
-                    HandRangeTokenKind::SingleCardPair(card_pair),
-                    parse_probability(&s[4..]),
+        if !self.eat(b'-') {
+            return Ok(HandRangeTokenKind::SingleRankPair(start));
+        }
+
+        let high_bottom = self.rank()?;
+        let kicker_bottom = self.rank()?;
+        let (high_bottom, kicker_bottom) = (
+            high_bottom.min(kicker_bottom),
+            high_bottom.max(kicker_bottom),
+        );
+
+        match start {
+            RankPair::Pocket(_) if high_bottom == kicker_bottom => {
+                return Ok(HandRangeTokenKind::DoubleClosedRankPairRange(
+                    RankPair::Pocket(high.min(high_bottom)),
+                    high.max(high_bottom),
                 ));
             }
+            RankPair::Suited(..) if !self.eat(b's') => return Err(self.unexpected()),
+            RankPair::Ofsuit(..) if !self.eat(b'o') => return Err(self.unexpected()),
+            _ => {}
         }
 
-        Err(())
-    }
-}
+        // the ends may also come in either order, e.g. "A2s-AKs" is the same as "AKs-A2s"
+        let ((high, kicker), (high_bottom, kicker_bottom)) =
+            if (high_bottom, kicker_bottom) < (high, kicker) {
+                ((high_bottom, kicker_bottom), (high, kicker))
+            } else {
+                ((high, kicker), (high_bottom, kicker_bottom))
+            };
+        let start = match start {
+            RankPair::Suited(..) => RankPair::Suited(high, kicker),
+            _ => RankPair::Ofsuit(high, kicker),
+        };
+
+        if high != high_bottom
+            && high < high_bottom
+            && connector_kicker(&start, high_bottom) == Some(kicker_bottom)
+        {
+            return Ok(HandRangeTokenKind::DoubleClosedConnectorRange(
+                start,
+                high_bottom,
+            ));
+        }
 
-fn parse_probability(value: &str) -> f32 {
-    let mut value = value;
+        if high == high_bottom && kicker < kicker_bottom {
+            return Ok(HandRangeTokenKind::DoubleClosedRankPairRange(
+                start,
+                kicker_bottom,
+            ));
+        }
 
-    if value.len() >= 1 && value.starts_with(":") {
-        value = &value[1..];
+        Err(ParseHandRangeTokenError::InvalidHand(0..self.pos))
     }
 
-    f32::from_str(value).unwrap_or(1.0)
+    // either nothing or a probability from 0 to 1 like ":0.25" or ":1.0"
+    fn probability(&mut self) -> Result<f32, ParseHandRangeTokenError> {
+        if self.pos == self.value.len() {
+            return Ok(1.0);
+        }
+
+        if !self.eat(b':') {
+            return Err(self.unexpected());
+        }
+
+        let value = &self.value[self.pos..];
+        let is_valid = match value.as_bytes() {
+            [b'0' | b'1'] => true,
+            [b'0', b'.', digits @ ..] => {
+                !digits.is_empty() && digits.iter().all(u8::is_ascii_digit)
+            }
+            [b'1', b'.', zeros @ ..] => !zeros.is_empty() && zeros.iter().all(|b| *b == b'0'),
+            _ => false,
+        };
+
+        if !is_valid {
+            return Err(ParseHandRangeTokenError::InvalidProbability(
+                self.pos..self.value.len(),
+            ));
+        }
+
+        Ok(f32::from_str(value).unwrap())
+    }
 }
 
 #[cfg(test)]
@@ -431,6 +761,116 @@ mod tests {
 
             assert_eq!(token.to_string(), "97o-94o");
         }
+
+        #[test]
+        fn it_formats_bottom_closed_suited_connector_range() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::BottomClosedConnectorRange(RankPair::Suited(
+                    Rank::Five,
+                    Rank::Four,
+                )),
+                1.0,
+            );
+
+            assert_eq!(token.to_string(), "54s+");
+        }
+
+        #[test]
+        fn it_formats_double_closed_suited_connector_range_with_prob() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::DoubleClosedConnectorRange(
+                    RankPair::Suited(Rank::Ten, Rank::Nine),
+                    Rank::Five,
+                ),
+                0.5,
+            );
+
+            assert_eq!(token.to_string(), "T9s-54s:0.5");
+        }
+
+        #[test]
+        fn it_formats_double_closed_ofsuit_gapper_range() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::DoubleClosedConnectorRange(
+                    RankPair::Ofsuit(Rank::Ten, Rank::Eight),
+                    Rank::Five,
+                ),
+                1.0,
+            );
+
+            assert_eq!(token.to_string(), "T8o-53o");
+        }
+
+        #[test]
+        fn it_formats_connector_range_without_kicker_when_end_is_not_reached() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::DoubleClosedConnectorRange(
+                    RankPair::Suited(Rank::Ten, Rank::Nine),
+                    Rank::King,
+                ),
+                1.0,
+            );
+
+            assert_eq!(token.to_string(), "T9s-K");
+
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::DoubleClosedConnectorRange(
+                    RankPair::Pocket(Rank::Ten),
+                    Rank::Five,
+                ),
+                1.0,
+            );
+
+            assert_eq!(token.to_string(), "TT-5");
+        }
+
+        #[test]
+        fn it_formats_card_with_rank() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::CardWithRank(Card::new(Rank::Ace, Suit::Spade), Rank::King),
+                1.0,
+            );
+
+            assert_eq!(token.to_string(), "AsKx");
+        }
+
+        #[test]
+        fn it_formats_card_with_suit_with_prob() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::CardWithSuit(Card::new(Rank::Ace, Suit::Heart), Suit::Heart),
+                0.5,
+            );
+
+            assert_eq!(token.to_string(), "AhXh:0.5");
+        }
+
+        #[test]
+        fn it_formats_card_with_any() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::CardWithAny(Card::new(Rank::King, Suit::Heart)),
+                1.0,
+            );
+
+            assert_eq!(token.to_string(), "KhXx");
+        }
+
+        #[test]
+        fn it_formats_rank_with_any() {
+            assert_eq!(
+                HandRangeToken::new(HandRangeTokenKind::RankWithAny(Rank::Ace), 1.0).to_string(),
+                "Ax"
+            );
+            assert_eq!(
+                HandRangeToken::new(HandRangeTokenKind::SuitedRankWithAny(Rank::Ace), 1.0)
+                    .to_string(),
+                "Axs"
+            );
+            assert_eq!(
+                HandRangeToken::new(HandRangeTokenKind::OfsuitRankWithAny(Rank::Ace), 0.3)
+                    .to_string(),
+                "Axo:0.3"
+            );
+        }
     }
 
     mod into_iter {
@@ -575,6 +1015,133 @@ mod tests {
 
             assert_debug_snapshot!(tokens);
         }
+
+        #[test]
+        fn it_iterates_bottom_closed_suited_connector_range() {
+            let rank_pairs = HandRangeToken::new(
+                HandRangeTokenKind::BottomClosedConnectorRange(RankPair::Suited(
+                    Rank::Nine,
+                    Rank::Eight,
+                )),
+                1.0,
+            )
+            .into_iter()
+            .map(|(cp, _)| cp)
+            .collect::<Vec<CardPair>>();
+
+            assert_eq!(
+                rank_pairs,
+                [
+                    RankPair::Suited(Rank::Ace, Rank::King),
+                    RankPair::Suited(Rank::King, Rank::Queen),
+                    RankPair::Suited(Rank::Queen, Rank::Jack),
+                    RankPair::Suited(Rank::Jack, Rank::Ten),
+                    RankPair::Suited(Rank::Ten, Rank::Nine),
+                    RankPair::Suited(Rank::Nine, Rank::Eight),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<CardPair>>()
+            );
+        }
+
+        #[test]
+        fn it_iterates_double_closed_ofsuit_gapper_range() {
+            let rank_pairs = HandRangeToken::new(
+                HandRangeTokenKind::DoubleClosedConnectorRange(
+                    RankPair::Ofsuit(Rank::Ten, Rank::Eight),
+                    Rank::Seven,
+                ),
+                0.5,
+            )
+            .into_iter()
+            .collect::<Vec<(CardPair, f32)>>();
+
+            assert_eq!(
+                rank_pairs,
+                [
+                    RankPair::Ofsuit(Rank::Ten, Rank::Eight),
+                    RankPair::Ofsuit(Rank::Nine, Rank::Seven),
+                    RankPair::Ofsuit(Rank::Eight, Rank::Six),
+                    RankPair::Ofsuit(Rank::Seven, Rank::Five),
+                ]
+                .into_iter()
+                .flatten()
+                .map(|cp| (cp, 0.5))
+                .collect::<Vec<(CardPair, f32)>>()
+            );
+        }
+
+        #[test]
+        fn it_iterates_card_with_rank() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::CardWithRank(Card::new(Rank::Ace, Suit::Spade), Rank::King),
+                1.0,
+            );
+
+            let tokens: Vec<(CardPair, f32)> = token.into_iter().collect();
+
+            assert_debug_snapshot!(tokens);
+        }
+
+        #[test]
+        fn it_iterates_card_with_same_rank() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::CardWithRank(Card::new(Rank::Jack, Suit::Heart), Rank::Jack),
+                1.0,
+            );
+
+            let tokens: Vec<(CardPair, f32)> = token.into_iter().collect();
+
+            assert_debug_snapshot!(tokens);
+        }
+
+        #[test]
+        fn it_iterates_card_with_suit() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::CardWithSuit(Card::new(Rank::Ace, Suit::Heart), Suit::Heart),
+                0.5,
+            );
+
+            let tokens: Vec<(CardPair, f32)> = token.into_iter().collect();
+
+            assert_debug_snapshot!(tokens);
+        }
+
+        #[test]
+        fn it_iterates_card_with_any() {
+            let token = HandRangeToken::new(
+                HandRangeTokenKind::CardWithAny(Card::new(Rank::King, Suit::Heart)),
+                1.0,
+            );
+
+            let tokens: Vec<(CardPair, f32)> = token.into_iter().collect();
+
+            assert_eq!(tokens.len(), 51);
+            assert!(tokens
+                .iter()
+                .all(|(cp, _)| cp[0] == Card::new(Rank::King, Suit::Heart)
+                    || cp[1] == Card::new(Rank::King, Suit::Heart)));
+        }
+
+        #[test]
+        fn it_iterates_rank_with_any() {
+            let any = HandRangeToken::new(HandRangeTokenKind::RankWithAny(Rank::Ace), 1.0)
+                .into_iter()
+                .collect::<Vec<(CardPair, f32)>>();
+            let suited = HandRangeToken::new(HandRangeTokenKind::SuitedRankWithAny(Rank::Ace), 1.0)
+                .into_iter()
+                .collect::<Vec<(CardPair, f32)>>();
+            let ofsuit = HandRangeToken::new(HandRangeTokenKind::OfsuitRankWithAny(Rank::Ace), 1.0)
+                .into_iter()
+                .collect::<Vec<(CardPair, f32)>>();
+
+            assert_eq!(any.len(), 192);
+            assert_eq!(suited.len(), 48);
+            assert_eq!(ofsuit.len(), 144);
+            assert!(suited.iter().all(|(cp, _)| cp[0].suit() == cp[1].suit()));
+            assert!(ofsuit.iter().all(|(cp, _)| cp[0].suit() != cp[1].suit()));
+        }
     }
 
     mod from_str {
@@ -865,6 +1432,123 @@ mod tests {
             );
         }
 
+        #[test]
+        fn it_parses_str_suited_five_four_plus() {
+            assert_eq!(
+                "54s+".parse::<HandRangeToken>().unwrap(),
+                HandRangeToken::new(
+                    HandRangeTokenKind::BottomClosedConnectorRange(RankPair::Suited(
+                        Rank::Five,
+                        Rank::Four
+                    )),
+                    1.0
+                )
+            );
+        }
+
+        #[test]
+        fn it_parses_str_suited_ten_nine_to_five_four_with_prob() {
+            assert_eq!(
+                "T9s-54s:0.5".parse::<HandRangeToken>().unwrap(),
+                HandRangeToken::new(
+                    HandRangeTokenKind::DoubleClosedConnectorRange(
+                        RankPair::Suited(Rank::Ten, Rank::Nine),
+                        Rank::Five
+                    ),
+                    0.5
+                )
+            );
+        }
+
+        #[test]
+        fn it_parses_str_ofsuit_ten_eight_to_five_trey() {
+            assert_eq!(
+                "T8o-53o".parse::<HandRangeToken>().unwrap(),
+                HandRangeToken::new(
+                    HandRangeTokenKind::DoubleClosedConnectorRange(
+                        RankPair::Ofsuit(Rank::Ten, Rank::Eight),
+                        Rank::Five
+                    ),
+                    1.0
+                )
+            );
+        }
+
+        #[test]
+        fn it_fails_parsing_connector_range_with_different_gaps() {
+            assert!("T9s-53s".parse::<HandRangeToken>().is_err());
+            assert!("53s-T9s".parse::<HandRangeToken>().is_err());
+            assert!("T9s-54o".parse::<HandRangeToken>().is_err());
+        }
+
+        #[test]
+        fn it_parses_str_ace_spade_any_king() {
+            assert_eq!(
+                "AsKx".parse::<HandRangeToken>().unwrap(),
+                HandRangeToken::new(
+                    HandRangeTokenKind::CardWithRank(Card::new(Rank::Ace, Suit::Spade), Rank::King),
+                    1.0
+                )
+            );
+        }
+
+        #[test]
+        fn it_parses_str_ace_heart_any_heart_with_prob() {
+            assert_eq!(
+                "AhXh:0.5".parse::<HandRangeToken>().unwrap(),
+                HandRangeToken::new(
+                    HandRangeTokenKind::CardWithSuit(
+                        Card::new(Rank::Ace, Suit::Heart),
+                        Suit::Heart
+                    ),
+                    0.5
+                )
+            );
+        }
+
+        #[test]
+        fn it_parses_str_king_heart_any() {
+            assert_eq!(
+                "KhXx".parse::<HandRangeToken>().unwrap(),
+                HandRangeToken::new(
+                    HandRangeTokenKind::CardWithAny(Card::new(Rank::King, Suit::Heart)),
+                    1.0
+                )
+            );
+        }
+
+        #[test]
+        fn it_parses_str_ace_any() {
+            assert_eq!(
+                "Ax".parse::<HandRangeToken>().unwrap(),
+                HandRangeToken::new(HandRangeTokenKind::RankWithAny(Rank::Ace), 1.0)
+            );
+            assert_eq!(
+                "Axs".parse::<HandRangeToken>().unwrap(),
+                HandRangeToken::new(HandRangeTokenKind::SuitedRankWithAny(Rank::Ace), 1.0)
+            );
+            assert_eq!(
+                "AXo:0.25".parse::<HandRangeToken>().unwrap(),
+                HandRangeToken::new(HandRangeTokenKind::OfsuitRankWithAny(Rank::Ace), 0.25)
+            );
+        }
+
+        #[test]
+        fn it_fails_parsing_rank_wildcard_with_rank() {
+            assert!("AsXK".parse::<HandRangeToken>().is_err());
+            assert!("Axx".parse::<HandRangeToken>().is_err());
+        }
+
+        #[test]
+        fn it_fails_parsing_probability_above_one() {
+            assert!(HandRangeToken::from_str("AA:1.5").is_err());
+            assert!(HandRangeToken::from_str("AKs+:1.01").is_err());
+            assert_eq!(
+                HandRangeToken::from_str("AA:1.0").unwrap().probability(),
+                1.0
+            );
+        }
+
         #[test]
         fn it_fails_parsing_qwe() {
             assert!("qwe".parse::<HandRangeToken>().is_err());
@@ -879,5 +1563,48 @@ mod tests {
         fn it_fails_parsing_jj_plus_plus() {
             assert!("JJ++".parse::<HandRangeToken>().is_err());
         }
+
+        #[test]
+        fn it_returns_error_with_span() {
+            assert_eq!(
+                "AKx".parse::<HandRangeToken>().unwrap_err(),
+                ParseHandRangeTokenError::UnexpectedChar(2..3)
+            );
+            assert_eq!(
+                "A♠K".parse::<HandRangeToken>().unwrap_err(),
+                ParseHandRangeTokenError::UnexpectedChar(1..4)
+            );
+            assert_eq!(
+                "AsK".parse::<HandRangeToken>().unwrap_err(),
+                ParseHandRangeTokenError::UnexpectedEnd(3..3)
+            );
+            assert_eq!(
+                "AA:0.".parse::<HandRangeToken>().unwrap_err(),
+                ParseHandRangeTokenError::InvalidProbability(3..5)
+            );
+            assert_eq!(
+                "T9s-53s:0.5".parse::<HandRangeToken>().unwrap_err(),
+                ParseHandRangeTokenError::InvalidHand(0..7)
+            );
+        }
+
+        #[test]
+        fn it_parses_reversed_ranks() {
+            let parse = |value: &str| value.parse::<HandRangeToken>().unwrap();
+
+            assert_eq!(parse("KAs"), parse("AKs"));
+            assert_eq!(parse("KAs+"), parse("AKs+"));
+            assert_eq!(parse("23s+"), parse("32s+"));
+            assert_eq!(parse("22-AA"), parse("AA-22"));
+            assert_eq!(parse("A2s-AKs"), parse("AKs-A2s"));
+            assert_eq!(parse("2Ao-KAo"), parse("AKo-A2o"));
+            assert_eq!(parse("54s-T9s"), parse("T9s-54s"));
+            assert_eq!(parse("TT-TT").into_iter().count(), 6);
+            assert_eq!(parse("22-AA").to_string(), "AA-22");
+            assert_eq!(
+                "QKo-Q9o".parse::<HandRangeToken>().unwrap_err(),
+                ParseHandRangeTokenError::InvalidHand(0..7)
+            );
+        }
     }
 }
@@ -0,0 +1,562 @@
+use super::range_grid::grid_rank_pair;
+use super::{CardPair, HandRange, HandRangeToken, InvalidHandRangeError};
+use crate::card::{Card, RankRange};
+use alloc::string::{String, ToString};
+use alloc::{format, vec, vec::Vec};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RangeFormat {
+    // e.g. "AA,AKs:0.5,AhKh:0.25", weights from 0 to 1
+    PioSolver,
+    // 1326 weights separated by whitespace in the order of "2d2c 2h2c 2h2d 2s2c ... AsAh"
+    PioSolverWeights,
+    // e.g. "AA,[50]AKs,AQs[/50]", weights in percentage
+    GtoPlus,
+    // e.g. "AA, AKs, AhKh". weights are not supported, so ranges with weights other than 1 can't
+    // be written.
+    Flopzilla,
+}
+
+impl HandRange {
+    pub fn from_range_format(
+        value: &str,
+        format: RangeFormat,
+    ) -> Result<HandRange, ParseRangeFormatError> {
+        match format {
+            RangeFormat::PioSolver => parse_pio_solver(value),
+            RangeFormat::PioSolverWeights => parse_pio_solver_weights(value),
+            RangeFormat::GtoPlus => parse_gto_plus(value),
+            RangeFormat::Flopzilla => parse_flopzilla(value),
+        }
+    }
+
+    // weights must be between 0 and 1
+    pub fn to_range_format(&self, format: RangeFormat) -> Result<String, WriteRangeFormatError> {
+        self.validate()
+            .map_err(WriteRangeFormatError::InvalidHandRange)?;
+
+        Ok(match format {
+            RangeFormat::PioSolver => hand_classes(self)
+                .into_iter()
+                .map(|(hand, weight)| {
+                    if weight == 1.0 {
+                        hand
+                    } else {
+                        format!("{}:{}", hand, weight)
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(","),
+            RangeFormat::PioSolverWeights => pio_solver_card_pairs()
+                .into_iter()
+                .map(|card_pair| {
+                    self.card_pairs()
+                        .get(&card_pair)
+                        .map_or("0".to_string(), |weight| weight.to_string())
+                })
+                .collect::<Vec<String>>()
+                .join(" "),
+            RangeFormat::GtoPlus => {
+                let classes = hand_classes(self);
+                let mut weights = classes
+                    .iter()
+                    .map(|(_, weight)| *weight)
+                    .filter(|weight| *weight != 1.0)
+                    .collect::<Vec<f32>>();
+
+                weights.sort_by(|a, b| b.total_cmp(a));
+                weights.dedup();
+
+                let mut groups = classes
+                    .iter()
+                    .filter(|(_, weight)| *weight == 1.0)
+                    .map(|(hand, _)| hand.clone())
+                    .collect::<Vec<String>>();
+
+                for weight in weights {
+                    let percentage = format_percentage(weight);
+                    let hands = classes
+                        .iter()
+                        .filter(|(_, w)| *w == weight)
+                        .map(|(hand, _)| hand.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(",");
+
+                    groups.push(format!("[{}]{}[/{}]", percentage, hands, percentage));
+                }
+
+                groups.join(",")
+            }
+            RangeFormat::Flopzilla => {
+                let classes = hand_classes(self);
+
+                if let Some((hand, weight)) = classes.iter().find(|(_, weight)| *weight != 1.0) {
+                    return Err(WriteRangeFormatError::UnsupportedWeight(
+                        hand.clone(),
+                        *weight,
+                    ));
+                }
+
+                classes
+                    .into_iter()
+                    .map(|(hand, _)| hand)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            }
+        })
+    }
+}
+
+// rank pairs in the row-major order of the 13x13 grid (AA, AKs, ..., A2s, AKo, KK, ...). rank
+// pairs whose combos don't share the same weight are written as individual card pairs.
+fn hand_classes(hand_range: &HandRange) -> Vec<(String, f32)> {
+    let card_pairs = hand_range.card_pairs();
+    let mut classes = vec![];
+
+    for row in RankRange::all() {
+        for column in RankRange::all() {
+            let rank_pair = grid_rank_pair(row, column);
+            let weights = rank_pair
+                .into_iter()
+                .map(|card_pair| card_pairs.get(&card_pair).copied().unwrap_or(0.0))
+                .collect::<Vec<f32>>();
+
+            if weights.iter().all(|weight| *weight == weights[0]) {
+                if weights[0] > 0.0 {
+                    classes.push((rank_pair.to_string(), weights[0]));
+                }
+
+                continue;
+            }
+
+            for (card_pair, weight) in rank_pair.into_iter().zip(weights) {
+                if weight > 0.0 {
+                    classes.push((card_pair.to_string(), weight));
+                }
+            }
+        }
+    }
+
+    classes
+}
+
+fn pio_solver_card_pairs() -> Vec<CardPair> {
+    // pio orders cards from deuce to ace, and clubs, diamonds, hearts and spades in each rank.
+    // that is the reverse of the canonical card order.
+    let mut cards = Card::all();
+    cards.reverse();
+
+    let mut card_pairs = Vec::with_capacity(1326);
+
+    for i in 1..cards.len() {
+        for j in 0..i {
+            card_pairs.push(CardPair::new(cards[i], cards[j]));
+        }
+    }
+
+    card_pairs
+}
+
+fn format_percentage(weight: f32) -> String {
+    // weights are validated to be between 0 and 1 before writing, so adding a half and
+    // truncating rounds them like `f64::round`, which isn't available without std
+    let percentage = ((weight as f64 * 100.0 * 1000.0 + 0.5) as u64) as f64 / 1000.0;
+
+    percentage.to_string()
+}
+
+fn parse_weight(value: &str, scale: f32) -> Result<f32, ParseRangeFormatError> {
+    match f32::from_str(value.trim()) {
+        Ok(weight) if (0.0..=scale).contains(&weight) => Ok(weight / scale),
+        _ => Err(ParseRangeFormatError::InvalidWeight(value.to_string())),
+    }
+}
+
+// a hand class is either a range token of espada (e.g. "QQ+", "AKs", "AhKh") or a pair of
+// different ranks without suitedness (e.g. "AK") that means both suited and offsuit combos.
+fn parse_hand(value: &str) -> Result<Vec<CardPair>, ParseRangeFormatError> {
+    let hand = value.trim();
+    let error = || ParseRangeFormatError::InvalidHand(hand.to_string());
+
+    if hand.contains(':') {
+        return Err(error());
+    }
+
+    if let Ok(token) = HandRangeToken::from_str(hand) {
+        return Ok(token.into_iter().map(|(card_pair, _)| card_pair).collect());
+    }
+
+    if hand.len() == 2 && hand.is_ascii() && hand[0..1] != hand[1..2] {
+        if let (Ok(suited), Ok(ofsuit)) = (
+            HandRangeToken::from_str(&format!("{}s", hand)),
+            HandRangeToken::from_str(&format!("{}o", hand)),
+        ) {
+            return Ok(suited
+                .into_iter()
+                .chain(ofsuit)
+                .map(|(card_pair, _)| card_pair)
+                .collect());
+        }
+    }
+
+    Err(error())
+}
+
+fn items(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+}
+
+fn parse_pio_solver(value: &str) -> Result<HandRange, ParseRangeFormatError> {
+    let mut card_pairs = vec![];
+
+    for item in items(value) {
+        let (hand, weight) = match item.split_once(':') {
+            Some((hand, weight)) => (hand, parse_weight(weight, 1.0)?),
+            None => (item, 1.0),
+        };
+
+        for card_pair in parse_hand(hand)? {
+            card_pairs.push((card_pair, weight));
+        }
+    }
+
+    Ok(card_pairs
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0.0)
+        .collect())
+}
+
+fn parse_pio_solver_weights(value: &str) -> Result<HandRange, ParseRangeFormatError> {
+    let weights = value.split_whitespace().collect::<Vec<&str>>();
+
+    if weights.len() != 1326 {
+        return Err(ParseRangeFormatError::InvalidLength(weights.len()));
+    }
+
+    let mut card_pairs = vec![];
+
+    for (card_pair, weight) in pio_solver_card_pairs().into_iter().zip(weights) {
+        let weight = parse_weight(weight, 1.0)?;
+
+        if weight > 0.0 {
+            card_pairs.push((card_pair, weight));
+        }
+    }
+
+    Ok(card_pairs.into_iter().collect())
+}
+
+fn parse_gto_plus(value: &str) -> Result<HandRange, ParseRangeFormatError> {
+    let mut card_pairs = vec![];
+    let mut group: Option<(&str, f32)> = None;
+
+    for item in items(value) {
+        let mut hand = item;
+
+        if let Some(rest) = hand.strip_prefix('[') {
+            let (tag, rest) = rest
+                .split_once(']')
+                .ok_or_else(|| ParseRangeFormatError::UnbalancedTag(item.to_string()))?;
+
+            if group.is_some() {
+                return Err(ParseRangeFormatError::UnbalancedTag(item.to_string()));
+            }
+
+            group = Some((tag, parse_weight(tag, 100.0)?));
+            hand = rest;
+        }
+
+        let mut closing = false;
+
+        if let Some((rest, tag)) = hand.split_once("[/") {
+            match (group, tag.strip_suffix(']')) {
+                (Some((open, _)), Some(close)) if open == close => {
+                    closing = true;
+                    hand = rest;
+                }
+                _ => return Err(ParseRangeFormatError::UnbalancedTag(item.to_string())),
+            }
+        }
+
+        let weight = group.map_or(1.0, |(_, weight)| weight);
+
+        for card_pair in parse_hand(hand)? {
+            card_pairs.push((card_pair, weight));
+        }
+
+        if closing {
+            group = None;
+        }
+    }
+
+    if let Some((tag, _)) = group {
+        return Err(ParseRangeFormatError::UnbalancedTag(tag.to_string()));
+    }
+
+    Ok(card_pairs
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0.0)
+        .collect())
+}
+
+fn parse_flopzilla(value: &str) -> Result<HandRange, ParseRangeFormatError> {
+    let mut card_pairs = vec![];
+
+    for item in items(value) {
+        card_pairs.extend(parse_hand(item)?);
+    }
+
+    Ok(card_pairs.into_iter().collect())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseRangeFormatError {
+    InvalidHand(String),
+    InvalidWeight(String),
+    InvalidLength(usize),
+    UnbalancedTag(String),
+}
+
+impl Display for ParseRangeFormatError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            ParseRangeFormatError::InvalidHand(value) => {
+                write!(f, "{} is not a valid hand.", value)
+            }
+            ParseRangeFormatError::InvalidWeight(value) => {
+                write!(f, "{} is not a valid weight.", value)
+            }
+            ParseRangeFormatError::InvalidLength(len) => {
+                write!(f, "{} weights are given while 1326 are expected.", len)
+            }
+            ParseRangeFormatError::UnbalancedTag(value) => {
+                write!(f, "{} has an unbalanced weight tag.", value)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum WriteRangeFormatError {
+    InvalidHandRange(InvalidHandRangeError),
+    UnsupportedWeight(String, f32),
+}
+
+impl Display for WriteRangeFormatError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            WriteRangeFormatError::InvalidHandRange(error) => error.fmt(f),
+            WriteRangeFormatError::UnsupportedWeight(hand, weight) => {
+                write!(
+                    f,
+                    "{} has a weight of {}, which the format doesn't support.",
+                    hand, weight
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIO_SOLVER: &str = include_str!("fixtures/pio_solver.txt");
+    const PIO_SOLVER_WEIGHTS: &str = include_str!("fixtures/pio_solver_weights.txt");
+    const GTO_PLUS: &str = include_str!("fixtures/gto_plus.txt");
+    const FLOPZILLA: &str = include_str!("fixtures/flopzilla.txt");
+
+    fn hand_range() -> HandRange {
+        HandRange::from_str("QQ+,AKs,AQs:0.5,AKo:0.75,JhTh:0.25,7c6c").unwrap()
+    }
+
+    mod pio_solver {
+        use super::*;
+
+        #[test]
+        fn it_reads_golden_file() {
+            assert_eq!(
+                HandRange::from_range_format(PIO_SOLVER, RangeFormat::PioSolver).unwrap(),
+                hand_range()
+            );
+        }
+
+        #[test]
+        fn it_writes_golden_file() {
+            assert_eq!(
+                hand_range()
+                    .to_range_format(RangeFormat::PioSolver)
+                    .unwrap(),
+                PIO_SOLVER.trim_end()
+            );
+        }
+
+        #[test]
+        fn it_reads_weights_in_any_precision() {
+            assert_eq!(
+                HandRange::from_range_format("AA:1.0, KK:.5, AK:0.125", RangeFormat::PioSolver)
+                    .unwrap(),
+                HandRange::from_str("AA,KK:0.5,AKs:0.125,AKo:0.125").unwrap()
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_weight_is_invalid() {
+            assert_eq!(
+                HandRange::from_range_format("AA:1.5", RangeFormat::PioSolver).unwrap_err(),
+                ParseRangeFormatError::InvalidWeight("1.5".to_string())
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_hand_is_invalid() {
+            assert_eq!(
+                HandRange::from_range_format("AA,AKx+", RangeFormat::PioSolver).unwrap_err(),
+                ParseRangeFormatError::InvalidHand("AKx+".to_string())
+            );
+        }
+    }
+
+    mod pio_solver_weights {
+        use super::*;
+
+        #[test]
+        fn it_reads_golden_file() {
+            assert_eq!(
+                HandRange::from_range_format(PIO_SOLVER_WEIGHTS, RangeFormat::PioSolverWeights)
+                    .unwrap(),
+                hand_range()
+            );
+        }
+
+        #[test]
+        fn it_writes_golden_file() {
+            assert_eq!(
+                hand_range()
+                    .to_range_format(RangeFormat::PioSolverWeights)
+                    .unwrap(),
+                PIO_SOLVER_WEIGHTS.trim_end()
+            );
+        }
+
+        #[test]
+        fn it_orders_card_pairs_from_deuces() {
+            let card_pairs = pio_solver_card_pairs();
+
+            assert_eq!(card_pairs.len(), 1326);
+            assert_eq!(card_pairs[0], CardPair::from_str("2d2c").unwrap());
+            assert_eq!(card_pairs[1], CardPair::from_str("2h2c").unwrap());
+            assert_eq!(card_pairs[6], CardPair::from_str("3c2c").unwrap());
+            assert_eq!(card_pairs[1325], CardPair::from_str("AsAh").unwrap());
+        }
+
+        #[test]
+        fn it_returns_error_when_length_is_invalid() {
+            assert_eq!(
+                HandRange::from_range_format("0 1 0", RangeFormat::PioSolverWeights).unwrap_err(),
+                ParseRangeFormatError::InvalidLength(3)
+            );
+        }
+    }
+
+    mod gto_plus {
+        use super::*;
+
+        #[test]
+        fn it_reads_golden_file() {
+            assert_eq!(
+                HandRange::from_range_format(GTO_PLUS, RangeFormat::GtoPlus).unwrap(),
+                hand_range()
+            );
+        }
+
+        #[test]
+        fn it_writes_golden_file() {
+            assert_eq!(
+                hand_range().to_range_format(RangeFormat::GtoPlus).unwrap(),
+                GTO_PLUS.trim_end()
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_weight_is_out_of_range() {
+            let card_pair = CardPair::from_str("AsAh").unwrap();
+
+            assert_eq!(
+                HandRange::from_iter([(card_pair, -0.5)]).to_range_format(RangeFormat::GtoPlus),
+                Err(WriteRangeFormatError::InvalidHandRange(
+                    InvalidHandRangeError::ProbabilityOutOfRange(card_pair, -0.5)
+                ))
+            );
+            assert_eq!(
+                HandRange::from_iter([(card_pair, f32::NAN)]).to_range_format(RangeFormat::GtoPlus),
+                Err(WriteRangeFormatError::InvalidHandRange(
+                    InvalidHandRangeError::NanProbability(card_pair)
+                ))
+            );
+        }
+
+        #[test]
+        fn it_reads_single_hand_group() {
+            assert_eq!(
+                HandRange::from_range_format("[12.5]AA[/12.5],KK", RangeFormat::GtoPlus).unwrap(),
+                HandRange::from_str("AA:0.125,KK").unwrap()
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_tag_is_not_closed() {
+            assert_eq!(
+                HandRange::from_range_format("[50]AA,KK", RangeFormat::GtoPlus).unwrap_err(),
+                ParseRangeFormatError::UnbalancedTag("50".to_string())
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_tags_mismatch() {
+            assert_eq!(
+                HandRange::from_range_format("[50]AA,KK[/25]", RangeFormat::GtoPlus).unwrap_err(),
+                ParseRangeFormatError::UnbalancedTag("KK[/25]".to_string())
+            );
+        }
+    }
+
+    mod flopzilla {
+        use super::*;
+
+        #[test]
+        fn it_reads_golden_file() {
+            assert_eq!(
+                HandRange::from_range_format(FLOPZILLA, RangeFormat::Flopzilla).unwrap(),
+                HandRange::from_str("QQ+,AKs,AQs,AKo,JhTh,7c6c").unwrap()
+            );
+        }
+
+        #[test]
+        fn it_writes_golden_file() {
+            assert_eq!(
+                HandRange::from_str("QQ+,AKs,AQs,AKo,JhTh,7c6c")
+                    .unwrap()
+                    .to_range_format(RangeFormat::Flopzilla)
+                    .unwrap(),
+                FLOPZILLA.trim_end()
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_writing_weights() {
+            assert_eq!(
+                hand_range().to_range_format(RangeFormat::Flopzilla),
+                Err(WriteRangeFormatError::UnsupportedWeight(
+                    "AQs".to_string(),
+                    0.5
+                ))
+            );
+        }
+    }
+}
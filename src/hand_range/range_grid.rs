@@ -0,0 +1,267 @@
+use super::{HandRange, RankPair};
+use crate::card::{Rank, RankRange};
+use alloc::{vec, vec::Vec};
+
+// the rank pair shown at the cell of the 13x13 grid. pocket pairs are on the diagonal, suited
+// rank pairs are above it and ofsuit rank pairs are below it.
+pub(crate) fn grid_rank_pair(row: Rank, column: Rank) -> RankPair {
+    if row == column {
+        RankPair::Pocket(row)
+    } else if row < column {
+        RankPair::Suited(row, column)
+    } else {
+        RankPair::Ofsuit(column, row)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RangeGridCell {
+    rank_pair: RankPair,
+    total_combos: u8,
+    selected_combos: u8,
+    weight_sum: f32,
+    mixed: bool,
+}
+
+impl RangeGridCell {
+    fn new(hand_range: &HandRange, rank_pair: RankPair) -> RangeGridCell {
+        let weights = rank_pair
+            .into_iter()
+            .map(|card_pair| {
+                hand_range
+                    .card_pairs()
+                    .get(&card_pair)
+                    .copied()
+                    .unwrap_or(0.0)
+            })
+            .collect::<Vec<f32>>();
+
+        RangeGridCell {
+            rank_pair,
+            total_combos: weights.len() as u8,
+            selected_combos: weights.iter().filter(|weight| **weight > 0.0).count() as u8,
+            weight_sum: weights.iter().sum(),
+            mixed: weights.iter().any(|weight| *weight != weights[0]),
+        }
+    }
+
+    pub fn rank_pair(&self) -> RankPair {
+        self.rank_pair
+    }
+
+    pub fn total_combos(&self) -> u8 {
+        self.total_combos
+    }
+
+    pub fn selected_combos(&self) -> u8 {
+        self.selected_combos
+    }
+
+    // averaged over every combo of the cell, so unselected combos count as zero
+    pub fn average_weight(&self) -> f32 {
+        self.weight_sum / self.total_combos as f32
+    }
+
+    // true when the combos of the cell don't share the same weight
+    pub fn is_mixed(&self) -> bool {
+        self.mixed
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RangeGrid {
+    cells: Vec<RangeGridCell>,
+}
+
+impl RangeGrid {
+    pub fn cell(&self, row: Rank, column: Rank) -> &RangeGridCell {
+        &self.cells[u8::from(row) as usize * 13 + u8::from(column) as usize]
+    }
+
+    pub fn get(&self, rank_pair: &RankPair) -> &RangeGridCell {
+        match *rank_pair {
+            RankPair::Pocket(rank) => self.cell(rank, rank),
+            RankPair::Suited(high, kicker) => self.cell(high, kicker),
+            RankPair::Ofsuit(high, kicker) => self.cell(kicker, high),
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[RangeGridCell]> {
+        self.cells.chunks(13)
+    }
+
+    pub fn weights(&self) -> [[f32; 13]; 13] {
+        let mut weights = [[0.0; 13]; 13];
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            weights[i / 13][i % 13] = cell.average_weight();
+        }
+
+        weights
+    }
+}
+
+impl From<&HandRange> for RangeGrid {
+    fn from(hand_range: &HandRange) -> Self {
+        let mut cells = Vec::with_capacity(169);
+
+        for row in RankRange::all() {
+            for column in RankRange::all() {
+                cells.push(RangeGridCell::new(hand_range, grid_rank_pair(row, column)));
+            }
+        }
+
+        RangeGrid { cells }
+    }
+}
+
+impl HandRange {
+    pub fn grid(&self) -> RangeGrid {
+        RangeGrid::from(self)
+    }
+}
+
+// every combo of a cell is given the weight of the cell. cells weighted zero are left out.
+impl From<[[f32; 13]; 13]> for HandRange {
+    fn from(weights: [[f32; 13]; 13]) -> Self {
+        let mut card_pairs = vec![];
+
+        for (row, row_weights) in RankRange::all().into_iter().zip(weights) {
+            for (column, weight) in RankRange::all().into_iter().zip(row_weights) {
+                if weight > 0.0 {
+                    for card_pair in grid_rank_pair(row, column) {
+                        card_pairs.push((card_pair, weight));
+                    }
+                }
+            }
+        }
+
+        card_pairs.into_iter().collect()
+    }
+}
+
+impl From<&RangeGrid> for HandRange {
+    fn from(grid: &RangeGrid) -> Self {
+        HandRange::from(grid.weights())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    mod cell {
+        use super::*;
+
+        #[test]
+        fn it_places_rank_pairs_in_grid() {
+            let grid = HandRange::empty().grid();
+
+            assert_eq!(
+                grid.cell(Rank::Ace, Rank::Ace).rank_pair(),
+                RankPair::Pocket(Rank::Ace)
+            );
+            assert_eq!(
+                grid.cell(Rank::Ace, Rank::King).rank_pair(),
+                RankPair::Suited(Rank::Ace, Rank::King)
+            );
+            assert_eq!(
+                grid.cell(Rank::King, Rank::Ace).rank_pair(),
+                RankPair::Ofsuit(Rank::Ace, Rank::King)
+            );
+            assert_eq!(
+                grid.cell(Rank::Deuce, Rank::Trey).rank_pair(),
+                RankPair::Ofsuit(Rank::Trey, Rank::Deuce)
+            );
+        }
+
+        #[test]
+        fn it_counts_combos() {
+            let grid = HandRange::from_str("AA,AKs,AsKh,AhKs").unwrap().grid();
+
+            assert_eq!(grid.get(&RankPair::Pocket(Rank::Ace)).total_combos(), 6);
+            assert_eq!(grid.get(&RankPair::Pocket(Rank::Ace)).selected_combos(), 6);
+            assert_eq!(
+                grid.get(&RankPair::Suited(Rank::Ace, Rank::King))
+                    .total_combos(),
+                4
+            );
+            assert_eq!(
+                grid.get(&RankPair::Ofsuit(Rank::Ace, Rank::King))
+                    .total_combos(),
+                12
+            );
+            assert_eq!(
+                grid.get(&RankPair::Ofsuit(Rank::Ace, Rank::King))
+                    .selected_combos(),
+                2
+            );
+            assert_eq!(grid.get(&RankPair::Pocket(Rank::King)).selected_combos(), 0);
+        }
+
+        #[test]
+        fn it_averages_weights() {
+            let grid = HandRange::from_str("AA:0.5,AsKs,AhKh,AdKd:0.5")
+                .unwrap()
+                .grid();
+            let pocket_aces = grid.get(&RankPair::Pocket(Rank::Ace));
+            let suited_ace_king = grid.get(&RankPair::Suited(Rank::Ace, Rank::King));
+
+            assert_eq!(pocket_aces.average_weight(), 0.5);
+            assert!(!pocket_aces.is_mixed());
+            assert_eq!(suited_ace_king.average_weight(), 0.625);
+            assert!(suited_ace_king.is_mixed());
+        }
+
+        #[test]
+        fn it_flags_partially_selected_cell_as_mixed() {
+            let grid = HandRange::from_str("AsAh").unwrap().grid();
+
+            assert!(grid.get(&RankPair::Pocket(Rank::Ace)).is_mixed());
+            assert!(!grid.get(&RankPair::Pocket(Rank::King)).is_mixed());
+        }
+    }
+
+    mod rows {
+        use super::*;
+
+        #[test]
+        fn it_iterates_13_rows_of_13_cells() {
+            let grid = HandRange::empty().grid();
+            let rows = grid.rows().collect::<Vec<_>>();
+
+            assert_eq!(rows.len(), 13);
+            assert!(rows.iter().all(|row| row.len() == 13));
+            assert_eq!(
+                rows[12][0].rank_pair(),
+                RankPair::Ofsuit(Rank::Ace, Rank::Deuce)
+            );
+        }
+    }
+
+    mod from_weights {
+        use super::*;
+
+        #[test]
+        fn it_builds_hand_range_from_weights() {
+            let mut weights = [[0.0; 13]; 13];
+
+            weights[0][0] = 1.0;
+            weights[0][1] = 0.5;
+            weights[1][0] = 0.25;
+
+            assert_eq!(
+                HandRange::from(weights),
+                HandRange::from_str("AA,AKs:0.5,AKo:0.25").unwrap()
+            );
+        }
+
+        #[test]
+        fn it_round_trips_uniform_range() {
+            let hand_range = HandRange::from_str("QQ+,AKs,AQs:0.5,KJo:0.75").unwrap();
+
+            assert_eq!(HandRange::from(&hand_range.grid()), hand_range);
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use crate::card::{Card, Rank, Suit};
 use crate::hand_range::CardPair;
-use std::fmt::Display;
+use alloc::vec;
+use core::fmt::Display;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub enum RankPair {
     Pocket(Rank),
     Suited(Rank, Rank),
@@ -10,7 +11,7 @@ pub enum RankPair {
 }
 
 impl Display for RankPair {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             RankPair::Pocket(rank) => write!(f, "{}{}", rank, rank),
             RankPair::Suited(high, kicker) => write!(f, "{}{}s", high, kicker),
@@ -19,9 +20,29 @@ impl Display for RankPair {
     }
 }
 
+impl From<&CardPair> for RankPair {
+    fn from(card_pair: &CardPair) -> Self {
+        let (high, kicker) = (card_pair[0], card_pair[1]);
+
+        if high.rank() == kicker.rank() {
+            RankPair::Pocket(*high.rank())
+        } else if high.suit() == kicker.suit() {
+            RankPair::Suited(*high.rank(), *kicker.rank())
+        } else {
+            RankPair::Ofsuit(*high.rank(), *kicker.rank())
+        }
+    }
+}
+
+impl From<CardPair> for RankPair {
+    fn from(card_pair: CardPair) -> Self {
+        RankPair::from(&card_pair)
+    }
+}
+
 impl IntoIterator for RankPair {
     type Item = CardPair;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = alloc::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
         match self {
@@ -85,6 +106,27 @@ impl IntoIterator for RankPair {
 mod tests {
     use super::*;
 
+    mod from_card_pair {
+        use super::*;
+        use std::str::FromStr;
+
+        #[test]
+        fn it_returns_rank_pair_of_card_pair() {
+            assert_eq!(
+                RankPair::from(CardPair::from_str("JhJs").unwrap()),
+                RankPair::Pocket(Rank::Jack)
+            );
+            assert_eq!(
+                RankPair::from(CardPair::from_str("TdQd").unwrap()),
+                RankPair::Suited(Rank::Queen, Rank::Ten)
+            );
+            assert_eq!(
+                RankPair::from(CardPair::from_str("5c9h").unwrap()),
+                RankPair::Ofsuit(Rank::Nine, Rank::Five)
+            );
+        }
+    }
+
     mod into_iter {
         use super::*;
 
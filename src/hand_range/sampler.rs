@@ -0,0 +1,334 @@
+use super::{CardPair, HandRange};
+use crate::card::CardSet;
+use alloc::{vec, vec::Vec};
+use rand::Rng;
+
+// the number of draws rejected by blockers before falling back to scanning the whole table
+const MAX_REJECTIONS: usize = 64;
+
+// the number of attempts to deal non-colliding card pairs to every player at once
+const MAX_JOINT_ATTEMPTS: usize = 10_000;
+
+// draws card pairs in proportion to their weights in O(1) with walker's alias method. build it
+// once and reuse it across draws. a range whose total weight isn't finite can't be drawn from in
+// proportion and makes an empty sampler.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HandRangeSampler {
+    card_pairs: Vec<CardPair>,
+    weights: Vec<f32>,
+    thresholds: Vec<f64>,
+    aliases: Vec<usize>,
+}
+
+impl HandRangeSampler {
+    pub fn new(hand_range: &HandRange) -> HandRangeSampler {
+        let (mut card_pairs, mut weights): (Vec<CardPair>, Vec<f32>) = hand_range
+            .iter()
+            .filter(|(_, probability)| **probability > 0.0)
+            .map(|(card_pair, probability)| (*card_pair, *probability))
+            .unzip();
+        let total: f64 = weights.iter().map(|weight| *weight as f64).sum();
+
+        if !total.is_finite() {
+            card_pairs.clear();
+            weights.clear();
+        }
+
+        let len = weights.len();
+        let mut scaled = weights
+            .iter()
+            .map(|weight| *weight as f64 * len as f64 / total)
+            .collect::<Vec<f64>>();
+        let mut thresholds = vec![1.0; len];
+        let mut aliases = (0..len).collect::<Vec<usize>>();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..len).partition(|i| scaled[*i] < 1.0);
+
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            large.pop();
+
+            thresholds[s] = scaled[s];
+            aliases[s] = l;
+            scaled[l] += scaled[s] - 1.0;
+
+            if scaled[l] < 1.0 {
+                small.push(l);
+            } else {
+                large.push(l);
+            }
+        }
+
+        HandRangeSampler {
+            card_pairs,
+            weights,
+            thresholds,
+            aliases,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.card_pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.card_pairs.is_empty()
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<CardPair> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let i = rng.gen_range(0..self.len());
+
+        if rng.gen::<f64>() < self.thresholds[i] {
+            Some(self.card_pairs[i])
+        } else {
+            Some(self.card_pairs[self.aliases[i]])
+        }
+    }
+
+    // draws a card pair that doesn't contain any of the dead cards. returns `None` when every
+    // card pair is blocked.
+    pub fn sample_excluding<R: Rng + ?Sized>(
+        &self,
+        dead_cards: &CardSet,
+        rng: &mut R,
+    ) -> Option<CardPair> {
+        for _ in 0..MAX_REJECTIONS {
+            let card_pair = self.sample(rng)?;
+
+            if dead_cards.is_disjoint(&CardSet::from(card_pair)) {
+                return Some(card_pair);
+            }
+        }
+
+        // most of the weight is blocked. pick one from the cumulative weights of the rest.
+        let candidates = self
+            .card_pairs
+            .iter()
+            .zip(&self.weights)
+            .filter(|(card_pair, _)| dead_cards.is_disjoint(&CardSet::from(**card_pair)))
+            .collect::<Vec<(&CardPair, &f32)>>();
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let total: f64 = candidates.iter().map(|(_, weight)| **weight as f64).sum();
+        let mut point = rng.gen_range(0.0..total);
+
+        for (card_pair, weight) in &candidates {
+            if point < **weight as f64 {
+                return Some(**card_pair);
+            }
+
+            point -= **weight as f64;
+        }
+
+        Some(*candidates[candidates.len() - 1].0)
+    }
+
+    // draws a card pair for each sampler so that no card is shared between players or with the
+    // dead cards. the whole deal is redrawn on a collision, which keeps the joint distribution
+    // exact. returns `None` when no deal is found.
+    pub fn sample_jointly<R: Rng + ?Sized>(
+        samplers: &[HandRangeSampler],
+        dead_cards: &CardSet,
+        rng: &mut R,
+    ) -> Option<Vec<CardPair>> {
+        let mut card_pairs = Vec::with_capacity(samplers.len());
+
+        'attempt: for _ in 0..MAX_JOINT_ATTEMPTS {
+            let mut used = *dead_cards;
+
+            card_pairs.clear();
+
+            for sampler in samplers {
+                let card_pair = sampler.sample_excluding(dead_cards, rng)?;
+                let cards = CardSet::from(card_pair);
+
+                if !used.is_disjoint(&cards) {
+                    continue 'attempt;
+                }
+
+                used = used | cards;
+                card_pairs.push(card_pair);
+            }
+
+            return Some(card_pairs);
+        }
+
+        None
+    }
+}
+
+impl HandRange {
+    pub fn sampler(&self) -> HandRangeSampler {
+        HandRangeSampler::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{Card, Rank, Suit};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::str::FromStr;
+
+    mod sample {
+        use super::*;
+
+        #[test]
+        fn it_samples_in_proportion_to_weights() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let sampler = HandRange::from_str("AA,KK:0.5").unwrap().sampler();
+            let aces = (0..30000)
+                .filter(|_| sampler.sample(&mut rng).unwrap()[0].rank() == &Rank::Ace)
+                .count();
+
+            assert!((aces as f32 / 30000.0 - 2.0 / 3.0).abs() < 0.01);
+        }
+
+        #[test]
+        fn it_samples_every_card_pair() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let hand_range = HandRange::from_str("AKs:0.1,QQ:0.9,7d6h:0.3").unwrap();
+            let sampler = hand_range.sampler();
+            let sampled = (0..10000)
+                .map(|_| sampler.sample(&mut rng).unwrap())
+                .collect::<HandRange>();
+
+            assert_eq!(sampled.len(), hand_range.len());
+        }
+
+        #[test]
+        fn it_never_samples_card_pair_with_zero_probability() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let sampler = HandRange::from_str("AA:0,KK:0.5").unwrap().sampler();
+
+            assert_eq!(sampler.len(), 6);
+
+            for _ in 0..100 {
+                assert_eq!(sampler.sample(&mut rng).unwrap()[0].rank(), &Rank::King);
+            }
+        }
+
+        #[test]
+        fn it_samples_reproducibly_with_the_same_seed() {
+            let sampler = HandRange::from_str("22+,AJs+,KQo").unwrap().sampler();
+            let mut left = ChaCha8Rng::seed_from_u64(42);
+            let mut right = ChaCha8Rng::seed_from_u64(42);
+
+            for _ in 0..100 {
+                assert_eq!(sampler.sample(&mut left), sampler.sample(&mut right));
+            }
+        }
+
+        #[test]
+        fn it_returns_none_for_empty_range() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+            assert_eq!(HandRange::empty().sampler().sample(&mut rng), None);
+        }
+
+        #[test]
+        fn it_returns_none_when_total_weight_is_infinite() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let hand_range = [
+                (CardPair::from_str("AsAh").unwrap(), f32::INFINITY),
+                (CardPair::from_str("KsKh").unwrap(), 0.5),
+            ]
+            .into_iter()
+            .collect::<HandRange>();
+
+            assert_eq!(hand_range.sampler().sample(&mut rng), None);
+        }
+    }
+
+    mod sample_excluding {
+        use super::*;
+
+        #[test]
+        fn it_never_samples_blocked_card_pair() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let sampler = HandRange::from_str("AA,KK").unwrap().sampler();
+            let dead_cards = CardSet::from_str("AsAhKs").unwrap();
+
+            for _ in 0..1000 {
+                let card_pair = sampler.sample_excluding(&dead_cards, &mut rng).unwrap();
+
+                assert!(dead_cards.is_disjoint(&CardSet::from(card_pair)));
+            }
+        }
+
+        #[test]
+        fn it_samples_card_pair_when_almost_everything_is_blocked() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let sampler = HandRange::from_str("AA,KK,QQ,9d8d:0.01").unwrap().sampler();
+            let dead_cards = CardSet::from_str("AsAhAcKsKhKcQsQhQc").unwrap();
+
+            assert_eq!(
+                sampler.sample_excluding(&dead_cards, &mut rng),
+                Some(CardPair::new(
+                    Card::new(Rank::Nine, Suit::Diamond),
+                    Card::new(Rank::Eight, Suit::Diamond)
+                ))
+            );
+        }
+
+        #[test]
+        fn it_returns_none_when_every_card_pair_is_blocked() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let sampler = HandRange::from_str("AA").unwrap().sampler();
+
+            assert_eq!(
+                sampler.sample_excluding(&CardSet::from_str("AsAhAd").unwrap(), &mut rng),
+                None
+            );
+        }
+    }
+
+    mod sample_jointly {
+        use super::*;
+
+        #[test]
+        fn it_samples_card_pairs_without_collisions() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let samplers = vec![
+                HandRange::from_str("AA,KK").unwrap().sampler(),
+                HandRange::from_str("AA,AKs").unwrap().sampler(),
+                HandRange::from_str("KK,AKo").unwrap().sampler(),
+            ];
+            let dead_cards = CardSet::from_str("Qs").unwrap();
+
+            for _ in 0..100 {
+                let card_pairs =
+                    HandRangeSampler::sample_jointly(&samplers, &dead_cards, &mut rng).unwrap();
+                let cards = card_pairs
+                    .iter()
+                    .flat_map(|card_pair| [card_pair[0], card_pair[1]])
+                    .collect::<CardSet>();
+
+                assert_eq!(card_pairs.len(), 3);
+                assert_eq!(cards.len(), 6);
+            }
+        }
+
+        #[test]
+        fn it_returns_none_when_ranges_cannot_be_dealt_together() {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let samplers = vec![
+                HandRange::from_str("AsAh").unwrap().sampler(),
+                HandRange::from_str("AsAd").unwrap().sampler(),
+            ];
+
+            assert_eq!(
+                HandRangeSampler::sample_jointly(&samplers, &CardSet::empty(), &mut rng),
+                None
+            );
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use super::HandRange;
+use crate::card::{Card, CardSet};
+use crate::evaluator::{BoardEvaluator, MadeHandType};
+use crate::hand_range::Map;
+
+// the number of card pairs that can be dealt from a full deck. 52 choose 2.
+const ALL_CARD_PAIRS_LEN: f32 = 1326.0;
+
+impl HandRange {
+    // weighted number of combos. a combo included at 0.5 is counted as half a combo.
+    pub fn combos(&self) -> f32 {
+        self.iter().map(|(_, probability)| probability).sum()
+    }
+
+    pub fn percent_of_all_hands(&self) -> f32 {
+        self.combos() / ALL_CARD_PAIRS_LEN * 100.0
+    }
+
+    // weighted number of combos that can still be dealt once the board and dead cards are gone
+    pub fn combos_excluding(&self, cards: &CardSet) -> f32 {
+        self.excluding(cards).combos()
+    }
+
+    // weighted number of combos making each hand type on the full board. combos blocked by the
+    // board are not counted and hand types that no combo makes are left out.
+    pub fn made_hand_type_combos(&self, board: &[Card; 5]) -> Map<MadeHandType, f32> {
+        let mut combos = Map::default();
+
+        let evaluator = BoardEvaluator::new(board);
+
+        for (card_pair, probability) in self.excluding(&CardSet::from(*board)).iter() {
+            *combos
+                .entry(evaluator.evaluate(card_pair).hand_type())
+                .or_insert(0.0) += probability;
+        }
+
+        combos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+    use std::str::FromStr;
+
+    fn board(value: &str) -> [Card; 5] {
+        let cards = parse_cards(value)
+            .unwrap()
+            .into_iter()
+            .map(Option::unwrap)
+            .collect::<Vec<Card>>();
+
+        cards.try_into().unwrap()
+    }
+
+    mod combos {
+        use super::*;
+
+        #[test]
+        fn it_counts_combos() {
+            assert_eq!(HandRange::from_str("AA,AKs,AKo").unwrap().combos(), 22.0);
+        }
+
+        #[test]
+        fn it_weights_combos() {
+            assert_eq!(
+                HandRange::from_str("AA:0.5,AKs,7d6h:0.25")
+                    .unwrap()
+                    .combos(),
+                7.25
+            );
+        }
+
+        #[test]
+        fn it_counts_nothing_in_empty_range() {
+            assert_eq!(HandRange::empty().combos(), 0.0);
+        }
+    }
+
+    mod percent_of_all_hands {
+        use super::*;
+
+        #[test]
+        fn it_returns_100_for_any_two() {
+            assert_eq!(
+                HandRange::from_str("22+,A2s+,K2s+,Q2s+,J2s+,T2s+,92s+,82s+,72s+,62s+,52s+,42s+,32s,A2o+,K2o+,Q2o+,J2o+,T2o+,92o+,82o+,72o+,62o+,52o+,42o+,32o")
+                    .unwrap()
+                    .percent_of_all_hands(),
+                100.0
+            );
+        }
+
+        #[test]
+        fn it_returns_weighted_percentage() {
+            assert_eq!(
+                HandRange::from_str("AA:0.5,KK,AKs")
+                    .unwrap()
+                    .percent_of_all_hands(),
+                13.0 / 1326.0 * 100.0
+            );
+        }
+    }
+
+    mod combos_excluding {
+        use super::*;
+
+        #[test]
+        fn it_removes_combos_blocked_by_cards() {
+            let hand_range = HandRange::from_str("AA,KK:0.5,AKs").unwrap();
+
+            assert_eq!(
+                hand_range.combos_excluding(&CardSet::from_str("AsKh2c").unwrap()),
+                3.0 + 1.5 + 2.0
+            );
+        }
+    }
+
+    mod made_hand_type_combos {
+        use super::*;
+
+        #[test]
+        fn it_counts_combos_for_each_hand_type() {
+            let combos = HandRange::from_str("AA,KK:0.5,AKs,76s")
+                .unwrap()
+                .made_hand_type_combos(&board("KsQs8s5d4h"));
+
+            assert_eq!(combos.get(&MadeHandType::Pair), Some(&9.0));
+            assert_eq!(combos.get(&MadeHandType::Trips), Some(&1.5));
+            assert_eq!(combos.get(&MadeHandType::Straight), Some(&3.0));
+            assert_eq!(combos.get(&MadeHandType::Flush), Some(&1.0));
+            assert_eq!(combos.get(&MadeHandType::TwoPair), None);
+            assert_eq!(combos.get(&MadeHandType::HighCard), None);
+        }
+
+        #[test]
+        fn it_sums_up_to_combos_excluding_board() {
+            let hand_range = HandRange::from_str("22+,ATs+,KJo+:0.5").unwrap();
+            let board = board("Jh9h7c3d2s");
+
+            assert_eq!(
+                hand_range
+                    .made_hand_type_combos(&board)
+                    .values()
+                    .sum::<f32>(),
+                hand_range.combos_excluding(&CardSet::from(board))
+            );
+        }
+    }
+}
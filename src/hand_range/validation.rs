@@ -0,0 +1,180 @@
+use super::{CardPair, HandRange};
+use core::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum InvalidHandRangeError {
+    NanProbability(CardPair),
+    ProbabilityOutOfRange(CardPair, f32),
+    ZeroTotalProbability,
+}
+
+impl Display for InvalidHandRangeError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            InvalidHandRangeError::NanProbability(card_pair) => {
+                write!(f, "{} has a probability of NaN.", card_pair)
+            }
+            InvalidHandRangeError::ProbabilityOutOfRange(card_pair, probability) => {
+                write!(
+                    f,
+                    "{} has a probability of {}, which is not between 0 and 1.",
+                    card_pair, probability
+                )
+            }
+            InvalidHandRangeError::ZeroTotalProbability => {
+                write!(f, "the probabilities sum up to zero.")
+            }
+        }
+    }
+}
+
+impl HandRange {
+    // every probability must be between 0 and 1. card pairs are checked in ascending order so
+    // that the same range always reports the same error.
+    pub fn validate(&self) -> Result<(), InvalidHandRangeError> {
+        for (card_pair, probability) in self.iter() {
+            if probability.is_nan() {
+                return Err(InvalidHandRangeError::NanProbability(*card_pair));
+            }
+
+            if !(0.0..=1.0).contains(probability) {
+                return Err(InvalidHandRangeError::ProbabilityOutOfRange(
+                    *card_pair,
+                    *probability,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // probabilities are clamped into 0 to 1. card pairs with NaN are dropped.
+    pub fn clamped(&self) -> HandRange {
+        self.iter()
+            .filter(|(_, probability)| !probability.is_nan())
+            .map(|(card_pair, probability)| (*card_pair, probability.clamp(0.0, 1.0)))
+            .collect()
+    }
+
+    // probabilities are scaled so that they sum up to 1, which turns the range into the
+    // distribution of hole cards.
+    pub fn normalized(&self) -> Result<HandRange, InvalidHandRangeError> {
+        self.validate()?;
+
+        let total = self.combos();
+
+        if total <= 0.0 {
+            return Err(InvalidHandRangeError::ZeroTotalProbability);
+        }
+
+        Ok(self
+            .iter()
+            .map(|(card_pair, probability)| (*card_pair, probability / total))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn card_pair(value: &str) -> CardPair {
+        CardPair::from_str(value).unwrap()
+    }
+
+    mod validate {
+        use super::*;
+
+        #[test]
+        fn it_accepts_probabilities_between_zero_and_one() {
+            assert_eq!(
+                HandRange::from_str("AA:0,KK:0.5,QQ").unwrap().validate(),
+                Ok(())
+            );
+        }
+
+        #[test]
+        fn it_rejects_nan() {
+            let hand_range = HandRange::from_iter([(card_pair("AsAh"), f32::NAN)]);
+
+            assert_eq!(
+                hand_range.validate(),
+                Err(InvalidHandRangeError::NanProbability(card_pair("AsAh")))
+            );
+        }
+
+        #[test]
+        fn it_rejects_probabilities_out_of_range() {
+            let hand_range = HandRange::from_iter([
+                (card_pair("KsKh"), -0.5),
+                (card_pair("AsAh"), 1.5),
+                (card_pair("QsQh"), f32::INFINITY),
+            ]);
+
+            assert_eq!(
+                hand_range.validate(),
+                Err(InvalidHandRangeError::ProbabilityOutOfRange(
+                    card_pair("AsAh"),
+                    1.5
+                ))
+            );
+        }
+    }
+
+    mod clamped {
+        use super::*;
+
+        #[test]
+        fn it_clamps_probabilities() {
+            let hand_range = HandRange::from_iter([
+                (card_pair("AsAh"), 1.5),
+                (card_pair("KsKh"), -0.5),
+                (card_pair("QsQh"), 0.25),
+                (card_pair("JsJh"), f32::NAN),
+            ]);
+
+            assert_eq!(
+                hand_range.clamped(),
+                HandRange::from_str("AsAh,KsKh:0,QsQh:0.25").unwrap()
+            );
+        }
+    }
+
+    mod normalized {
+        use super::*;
+
+        #[test]
+        fn it_scales_probabilities_to_sum_up_to_one() {
+            let normalized = HandRange::from_str("AsAh,KsKh:0.5,QsQh:0.5")
+                .unwrap()
+                .normalized()
+                .unwrap();
+
+            assert_eq!(
+                normalized,
+                HandRange::from_str("AsAh:0.5,KsKh:0.25,QsQh:0.25").unwrap()
+            );
+            assert_eq!(normalized.combos(), 1.0);
+        }
+
+        #[test]
+        fn it_returns_error_when_range_is_empty() {
+            assert_eq!(
+                HandRange::empty().normalized(),
+                Err(InvalidHandRangeError::ZeroTotalProbability)
+            );
+        }
+
+        #[test]
+        fn it_returns_error_when_range_is_invalid() {
+            assert_eq!(
+                HandRange::from_iter([(card_pair("AsAh"), 2.0)]).normalized(),
+                Err(InvalidHandRangeError::ProbabilityOutOfRange(
+                    card_pair("AsAh"),
+                    2.0
+                ))
+            );
+        }
+    }
+}
@@ -1,3 +1,9 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub mod card;
 pub mod evaluator;
 pub mod hand_range;
+#[cfg(feature = "serde")]
+pub mod serialization;
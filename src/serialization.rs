@@ -0,0 +1,341 @@
+use crate::card::{Card, CardSet, Rank, Suit};
+use crate::evaluator::MadeHand;
+use crate::hand_range::{CardPair, HandRange};
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+struct FromStrVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T: FromStr> Visitor<'_> for FromStrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        T::from_str(v).map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+macro_rules! impl_serde_via_str {
+    ($t:ty, $expecting:expr) => {
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(FromStrVisitor {
+                    expecting: $expecting,
+                    marker: PhantomData,
+                })
+            }
+        }
+    };
+}
+
+impl_serde_via_str!(Rank, "a rank string like \"A\"");
+impl_serde_via_str!(Suit, "a suit string like \"s\"");
+impl_serde_via_str!(Card, "a card string like \"As\"");
+impl_serde_via_str!(CardPair, "a card pair string like \"AsKd\"");
+impl_serde_via_str!(CardSet, "a card set string like \"AsKd7c\"");
+impl_serde_via_str!(HandRange, "a hand range string like \"QQ+,AKs\"");
+
+impl Serialize for MadeHand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.power_index())
+    }
+}
+
+impl<'de> Deserialize<'de> for MadeHand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let power_index = u16::deserialize(deserializer)?;
+
+        MadeHand::try_from(power_index).map_err(|_| {
+            D::Error::invalid_value(
+                Unexpected::Unsigned(power_index as u64),
+                &"a power index from 1 to 7462",
+            )
+        })
+    }
+}
+
+// numeric representation that is smaller than the string one. use it through
+// `#[serde(with = "espada::serialization::compact")]`.
+pub mod compact {
+    use super::*;
+
+    pub trait Compact: Sized {
+        type Repr: Serialize + for<'de> Deserialize<'de>;
+
+        fn to_compact(&self) -> Self::Repr;
+
+        fn from_compact(repr: Self::Repr) -> Option<Self>;
+    }
+
+    pub fn serialize<T: Compact, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.to_compact().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: Compact, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::from_compact(T::Repr::deserialize(deserializer)?)
+            .ok_or_else(|| D::Error::custom("invalid compact representation"))
+    }
+
+    impl Compact for Rank {
+        type Repr = u8;
+
+        fn to_compact(&self) -> Self::Repr {
+            u8::from(self)
+        }
+
+        fn from_compact(repr: Self::Repr) -> Option<Self> {
+            (repr < 13).then(|| *Card::from_index(repr * 4).rank())
+        }
+    }
+
+    impl Compact for Suit {
+        type Repr = u8;
+
+        fn to_compact(&self) -> Self::Repr {
+            u8::from(self)
+        }
+
+        fn from_compact(repr: Self::Repr) -> Option<Self> {
+            (repr < 4).then(|| *Card::from_index(repr).suit())
+        }
+    }
+
+    impl Compact for Card {
+        type Repr = u8;
+
+        fn to_compact(&self) -> Self::Repr {
+            self.index()
+        }
+
+        fn from_compact(repr: Self::Repr) -> Option<Self> {
+            (repr < 52).then(|| Card::from_index(repr))
+        }
+    }
+
+    impl Compact for CardPair {
+        type Repr = [u8; 2];
+
+        fn to_compact(&self) -> Self::Repr {
+            [self[0].index(), self[1].index()]
+        }
+
+        fn from_compact(repr: Self::Repr) -> Option<Self> {
+            if repr[0] >= 52 || repr[1] >= 52 || repr[0] == repr[1] {
+                return None;
+            }
+
+            Some(CardPair::new(
+                Card::from_index(repr[0]),
+                Card::from_index(repr[1]),
+            ))
+        }
+    }
+
+    impl Compact for CardSet {
+        type Repr = u64;
+
+        fn to_compact(&self) -> Self::Repr {
+            u64::from(self)
+        }
+
+        fn from_compact(repr: Self::Repr) -> Option<Self> {
+            let card_set = CardSet::from(repr);
+
+            (u64::from(card_set) == repr).then_some(card_set)
+        }
+    }
+
+    impl Compact for HandRange {
+        type Repr = Vec<(u8, u8, f32)>;
+
+        fn to_compact(&self) -> Self::Repr {
+            self.iter()
+                .map(|(card_pair, probability)| {
+                    (card_pair[0].index(), card_pair[1].index(), *probability)
+                })
+                .collect()
+        }
+
+        fn from_compact(repr: Self::Repr) -> Option<Self> {
+            repr.into_iter()
+                .map(|(left, right, probability)| {
+                    CardPair::from_compact([left, right]).map(|card_pair| (card_pair, probability))
+                })
+                .collect()
+        }
+    }
+
+    impl Compact for MadeHand {
+        type Repr = u16;
+
+        fn to_compact(&self) -> Self::Repr {
+            self.power_index()
+        }
+
+        fn from_compact(repr: Self::Repr) -> Option<Self> {
+            MadeHand::try_from(repr).ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator::Showdown;
+
+    mod string {
+        use super::*;
+
+        #[test]
+        fn it_serializes_card() {
+            let card = Card::new(Rank::Ace, Suit::Spade);
+
+            assert_eq!(serde_json::to_string(&card).unwrap(), "\"As\"");
+            assert_eq!(serde_json::from_str::<Card>("\"As\"").unwrap(), card);
+        }
+
+        #[test]
+        fn it_serializes_rank_and_suit() {
+            assert_eq!(serde_json::to_string(&Rank::Ten).unwrap(), "\"T\"");
+            assert_eq!(serde_json::to_string(&Suit::Heart).unwrap(), "\"h\"");
+            assert_eq!(serde_json::from_str::<Rank>("\"T\"").unwrap(), Rank::Ten);
+            assert_eq!(serde_json::from_str::<Suit>("\"h\"").unwrap(), Suit::Heart);
+        }
+
+        #[test]
+        fn it_serializes_card_pair() {
+            let card_pair = CardPair::from_str("AsKd").unwrap();
+
+            assert_eq!(serde_json::to_string(&card_pair).unwrap(), "\"AsKd\"");
+            assert_eq!(
+                serde_json::from_str::<CardPair>("\"AsKd\"").unwrap(),
+                card_pair
+            );
+        }
+
+        #[test]
+        fn it_serializes_card_set() {
+            let card_set = CardSet::from_str("AsKd7c").unwrap();
+
+            assert_eq!(serde_json::to_string(&card_set).unwrap(), "\"AsKd7c\"");
+            assert_eq!(
+                serde_json::from_str::<CardSet>("\"AsKd7c\"").unwrap(),
+                card_set
+            );
+        }
+
+        #[test]
+        fn it_serializes_hand_range() {
+            let hand_range = HandRange::from_str("QQ+,AKs,AQo:0.5,7d6h:0.25").unwrap();
+            let json = serde_json::to_string(&hand_range).unwrap();
+
+            assert_eq!(json, "\"QQ+,AKs,AQo:0.5,7d6h:0.25\"");
+            assert_eq!(
+                serde_json::from_str::<HandRange>(&json).unwrap(),
+                hand_range
+            );
+        }
+
+        #[test]
+        fn it_serializes_made_hand() {
+            let made_hand = MadeHand::try_from(1609).unwrap();
+
+            assert_eq!(serde_json::to_string(&made_hand).unwrap(), "1609");
+            assert_eq!(serde_json::from_str::<MadeHand>("1609").unwrap(), made_hand);
+        }
+
+        #[test]
+        fn it_serializes_showdown() {
+            let showdown = Showdown::new(
+                vec![CardPair::from_str("AsKs").unwrap()],
+                [
+                    Card::new(Rank::Queen, Suit::Spade),
+                    Card::new(Rank::Jack, Suit::Spade),
+                    Card::new(Rank::Ten, Suit::Spade),
+                    Card::new(Rank::Deuce, Suit::Heart),
+                    Card::new(Rank::Deuce, Suit::Club),
+                ],
+                1.0,
+            )
+            .unwrap();
+
+            assert_eq!(
+                serde_json::to_string(&showdown).unwrap(),
+                "{\"board\":[\"Qs\",\"Js\",\"Ts\",\"2h\",\"2c\"],\"players\":[{\"hole_cards\":\"AsKs\",\"board\":[\"Qs\",\"Js\",\"Ts\",\"2h\",\"2c\"],\"hand\":1,\"win\":true}],\"probability\":1.0}"
+            );
+        }
+
+        #[test]
+        fn it_fails_deserializing_invalid_values() {
+            assert!(serde_json::from_str::<Card>("\"Aj\"").is_err());
+            assert!(serde_json::from_str::<CardPair>("\"AsAs1\"").is_err());
+            assert!(serde_json::from_str::<MadeHand>("0").is_err());
+            assert!(serde_json::from_str::<MadeHand>("7463").is_err());
+        }
+    }
+
+    mod compact {
+        use super::*;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Record {
+            #[serde(with = "super::super::compact")]
+            card: Card,
+            #[serde(with = "super::super::compact")]
+            card_pair: CardPair,
+            #[serde(with = "super::super::compact")]
+            card_set: CardSet,
+            #[serde(with = "super::super::compact")]
+            hand_range: HandRange,
+        }
+
+        #[test]
+        fn it_serializes_into_numbers() {
+            let record = Record {
+                card: Card::new(Rank::King, Suit::Heart),
+                card_pair: CardPair::from_str("AsKd").unwrap(),
+                card_set: CardSet::from_str("AsAh").unwrap(),
+                hand_range: HandRange::from_str("AhAd:0.5,KsKc").unwrap(),
+            };
+            let json = serde_json::to_string(&record).unwrap();
+
+            assert_eq!(
+                json,
+                "{\"card\":5,\"card_pair\":[0,6],\"card_set\":3,\"hand_range\":[[1,2,0.5],[4,7,1.0]]}"
+            );
+            assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+        }
+
+        #[test]
+        fn it_fails_deserializing_out_of_range_numbers() {
+            assert!(serde_json::from_str::<Record>(
+                "{\"card\":52,\"card_pair\":[0,6],\"card_set\":3,\"hand_range\":[]}"
+            )
+            .is_err());
+            assert!(serde_json::from_str::<Record>(
+                "{\"card\":5,\"card_pair\":[6,6],\"card_set\":3,\"hand_range\":[]}"
+            )
+            .is_err());
+        }
+    }
+}